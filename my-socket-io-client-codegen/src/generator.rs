@@ -0,0 +1,329 @@
+use std::collections::HashSet;
+
+use heck::ToSnakeCase;
+
+use crate::{rust_types::*, spec::*};
+
+struct GeneratedMethods {
+    names: HashSet<String>,
+    code: String,
+}
+
+impl GeneratedMethods {
+    fn push(&mut self, name: String, code: String) -> Result<(), String> {
+        if !self.names.insert(name.clone()) {
+            return Err(format!(
+                "Facade method {} is generated twice. Give the operations unique operationId",
+                name
+            ));
+        }
+
+        self.code.push_str(code.as_str());
+        Ok(())
+    }
+}
+
+pub fn generate_code(spec: &AsyncApiSpec) -> Result<String, String> {
+    let mut types = RustTypes::new(&spec.schemas);
+    let mut models = String::new();
+    let mut methods = GeneratedMethods {
+        names: HashSet::new(),
+        code: String::new(),
+    };
+
+    for channel in spec.channels.iter() {
+        for operation in channel.operations.iter() {
+            for message in operation.messages.iter() {
+                let base_name = format!(
+                    "{}{}",
+                    to_type_name(channel.namespace.as_str()),
+                    to_type_name(message.event_name.as_str())
+                );
+
+                let method_name = match (&operation.operation_id, operation.messages.len()) {
+                    (Some(operation_id), 1) => operation_id.to_snake_case(),
+                    _ => {
+                        let prefix = match operation.direction {
+                            OperationDirection::Subscribe => "on",
+                            OperationDirection::Publish => "emit",
+                        };
+
+                        format!(
+                            "{}_{}_{}",
+                            prefix,
+                            channel.namespace.to_snake_case(),
+                            message.event_name.to_snake_case()
+                        )
+                    }
+                };
+
+                let method_name = to_field_name(method_name.as_str());
+
+                let payload_type =
+                    types.resolve(&message.payload, format!("{}Payload", base_name).as_str())?;
+
+                let ack_type = match &message.ack {
+                    Some(ack) => {
+                        let ack_payload_type =
+                            types.resolve(ack, format!("{}AckPayload", base_name).as_str())?;
+                        let ack_type = format!("{}Ack", base_name);
+                        reserve(&mut types, ack_type.as_str())?;
+                        models.push_str(
+                            new_type(ack_type.as_str(), ack_payload_type.as_str()).as_str(),
+                        );
+                        Some(ack_type)
+                    }
+                    None => None,
+                };
+
+                match operation.direction {
+                    OperationDirection::Subscribe => {
+                        let event_type = format!("{}Event", base_name);
+                        reserve(&mut types, event_type.as_str())?;
+                        models.push_str(
+                            new_type(event_type.as_str(), payload_type.as_str()).as_str(),
+                        );
+                        models.push_str(
+                            subscribe_event_model(
+                                event_type.as_str(),
+                                channel.namespace.as_str(),
+                                message.event_name.as_str(),
+                            )
+                            .as_str(),
+                        );
+
+                        if let Some(ack_type) = ack_type.as_ref() {
+                            models.push_str(subscribe_out_model(ack_type.as_str()).as_str());
+                        }
+
+                        methods.push(
+                            method_name.clone(),
+                            subscribe_method(
+                                method_name.as_str(),
+                                event_type.as_str(),
+                                ack_type.as_deref().unwrap_or("()"),
+                            ),
+                        )?;
+                    }
+                    OperationDirection::Publish => {
+                        let request_type = format!("{}Request", base_name);
+                        reserve(&mut types, request_type.as_str())?;
+                        models.push_str(
+                            new_type(request_type.as_str(), payload_type.as_str()).as_str(),
+                        );
+                        models.push_str(
+                            rpc_in_model(
+                                request_type.as_str(),
+                                channel.namespace.as_str(),
+                                message.event_name.as_str(),
+                            )
+                            .as_str(),
+                        );
+
+                        if let Some(ack_type) = ack_type.as_ref() {
+                            models.push_str(rpc_out_model(ack_type.as_str()).as_str());
+                        }
+
+                        methods.push(
+                            method_name.clone(),
+                            publish_method(
+                                method_name.as_str(),
+                                request_type.as_str(),
+                                ack_type.as_deref(),
+                            ),
+                        )?;
+                    }
+                }
+            }
+        }
+    }
+
+    let facade_name = format!("{}SocketIoClient", to_type_name(spec.title.as_str()));
+
+    let mut result = String::new();
+    result.push_str("// Generated by my-socket-io-client-codegen. Do not edit.\n\n");
+    result.push_str(types.into_code().as_str());
+    result.push_str(models.as_str());
+    result.push_str(facade(facade_name.as_str(), methods.code.as_str()).as_str());
+
+    Ok(result)
+}
+
+fn reserve(types: &mut RustTypes, type_name: &str) -> Result<(), String> {
+    if !types.reserve_name(type_name) {
+        return Err(format!("Type name {} is generated twice", type_name));
+    }
+
+    Ok(())
+}
+
+fn new_type(type_name: &str, payload_type: &str) -> String {
+    format!(
+        "#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct {type_name}(pub {payload_type});
+
+"
+    )
+}
+
+fn subscribe_event_model(type_name: &str, namespace: &str, event_name: &str) -> String {
+    let try_deserialize = try_deserialize_method(type_name);
+
+    format!(
+        "impl my_socket_io_client::SocketIoSubscribeEventModel for {type_name} {{
+    const NAME_SPACE: &'static str = {namespace:?};
+    const EVENT_NAME: &'static str = {event_name:?};
+
+    fn deserialize(payload: &str) -> Self {{
+        match <Self as my_socket_io_client::SocketIoSubscribeEventModel>::try_deserialize(payload) {{
+            Ok(result) => result,
+            Err(err) => panic!(\"{{}}\", err),
+        }}
+    }}
+{try_deserialize}}}
+
+"
+    )
+}
+
+fn subscribe_out_model(type_name: &str) -> String {
+    let try_serialize = try_serialize_method(type_name);
+
+    format!(
+        "impl my_socket_io_client::SocketIoSubscribeOutModel for {type_name} {{
+    fn serialize(&self) -> String {{
+        match <Self as my_socket_io_client::SocketIoSubscribeOutModel>::try_serialize(self) {{
+            Ok(result) => result,
+            Err(err) => panic!(\"{{}}\", err),
+        }}
+    }}
+{try_serialize}}}
+
+"
+    )
+}
+
+fn rpc_in_model(type_name: &str, namespace: &str, event_name: &str) -> String {
+    let try_serialize = try_serialize_method(type_name);
+
+    format!(
+        "impl my_socket_io_client::SocketIoRpcInModel for {type_name} {{
+    const NAME_SPACE: &'static str = {namespace:?};
+    const EVENT_NAME: &'static str = {event_name:?};
+
+    fn serialize(&self) -> String {{
+        match <Self as my_socket_io_client::SocketIoRpcInModel>::try_serialize(self) {{
+            Ok(result) => result,
+            Err(err) => panic!(\"{{}}\", err),
+        }}
+    }}
+{try_serialize}}}
+
+"
+    )
+}
+
+fn rpc_out_model(type_name: &str) -> String {
+    let try_deserialize = try_deserialize_method(type_name);
+
+    format!(
+        "impl my_socket_io_client::SocketIoRpcOutModel for {type_name} {{
+    fn deserialize(payload: &str) -> Self {{
+        match <Self as my_socket_io_client::SocketIoRpcOutModel>::try_deserialize(payload) {{
+            Ok(result) => result,
+            Err(err) => panic!(\"{{}}\", err),
+        }}
+    }}
+{try_deserialize}}}
+
+"
+    )
+}
+
+fn try_deserialize_method(type_name: &str) -> String {
+    format!(
+        "
+    fn try_deserialize(payload: &str) -> Result<Self, String> {{
+        my_socket_io_client::serde_json::from_str(payload).map_err(|err| {{
+            format!(\"Can not deserialize {type_name}. Payload: '{{}}'. Err: {{}}\", payload, err)
+        }})
+    }}
+"
+    )
+}
+
+fn try_serialize_method(type_name: &str) -> String {
+    format!(
+        "
+    fn try_serialize(&self) -> Result<String, String> {{
+        my_socket_io_client::serde_json::to_string(self)
+            .map_err(|err| format!(\"Can not serialize {type_name}. Err: {{}}\", err))
+    }}
+"
+    )
+}
+
+fn subscribe_method(method_name: &str, event_type: &str, ack_type: &str) -> String {
+    format!(
+        "
+    pub async fn {method_name}(
+        &self,
+        callbacks: std::sync::Arc<
+            dyn my_socket_io_client::SocketIoEventSubscriberCallback<{event_type}, {ack_type}>
+                + Send
+                + Sync
+                + 'static,
+        >,
+    ) -> my_socket_io_client::SocketIoSubscription {{
+        self.client.register_subscriber(callbacks).await
+    }}
+"
+    )
+}
+
+fn publish_method(method_name: &str, request_type: &str, ack_type: Option<&str>) -> String {
+    match ack_type {
+        Some(ack_type) => format!(
+            "
+    pub async fn {method_name}(&self, model: &{request_type}) -> Result<{ack_type}, String> {{
+        let Some(connection) = self.client.get_connection().await else {{
+            return Err(\"Socket is not connected\".to_string());
+        }};
+
+        connection.send_event_with_ack(model).await
+    }}
+"
+        ),
+        None => format!(
+            "
+    pub async fn {method_name}(&self, model: &{request_type}) -> Result<(), String> {{
+        let Some(connection) = self.client.get_connection().await else {{
+            return Err(\"Socket is not connected\".to_string());
+        }};
+
+        connection.try_send_event_and_forget(model).await
+    }}
+"
+        ),
+    }
+}
+
+fn facade(facade_name: &str, methods: &str) -> String {
+    format!(
+        "pub struct {facade_name} {{
+    client: std::sync::Arc<my_socket_io_client::MySocketIoClient>,
+}}
+
+impl {facade_name} {{
+    pub fn new(client: std::sync::Arc<my_socket_io_client::MySocketIoClient>) -> Self {{
+        Self {{ client }}
+    }}
+
+    pub fn get_client(&self) -> &std::sync::Arc<my_socket_io_client::MySocketIoClient> {{
+        &self.client
+    }}
+{methods}}}
+"
+    )
+}
@@ -0,0 +1,45 @@
+mod generator;
+mod rust_types;
+mod spec;
+
+use std::path::Path;
+
+/// Generates models and the typed client facade from the AsyncAPI 2.x document (JSON or YAML)
+pub fn generate(spec: &str) -> Result<String, String> {
+    let spec = spec::AsyncApiSpec::parse(spec)?;
+    generator::generate_code(&spec)
+}
+
+pub fn generate_to_file(
+    spec_path: impl AsRef<Path>,
+    out_path: impl AsRef<Path>,
+) -> Result<(), String> {
+    let spec_path = spec_path.as_ref();
+
+    let spec = std::fs::read_to_string(spec_path)
+        .map_err(|err| format!("Can not read {}. Err: {}", spec_path.display(), err))?;
+
+    let code = generate(spec.as_str())?;
+
+    let out_path = out_path.as_ref();
+
+    std::fs::write(out_path, code)
+        .map_err(|err| format!("Can not write {}. Err: {}", out_path.display(), err))
+}
+
+/// To be called from build.rs. Writes `file_name` into OUT_DIR so it can be included with
+/// `include!(concat!(env!("OUT_DIR"), "/<file_name>"));`
+pub fn build(spec_path: &str, file_name: &str) {
+    println!("cargo:rerun-if-changed={}", spec_path);
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is not set. Call build from build.rs");
+
+    let out_path = Path::new(out_dir.as_str()).join(file_name);
+
+    if let Err(err) = generate_to_file(spec_path, out_path) {
+        panic!(
+            "Can not generate socket io client from {}. Err: {}",
+            spec_path, err
+        );
+    }
+}
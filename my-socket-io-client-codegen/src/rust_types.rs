@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+
+use heck::{ToPascalCase, ToSnakeCase};
+use serde_json::Value;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while",
+];
+
+pub fn to_type_name(src: &str) -> String {
+    let result = src.to_pascal_case();
+
+    if result.is_empty() {
+        return "Root".to_string();
+    }
+
+    if result.starts_with(|c: char| c.is_ascii_digit()) {
+        return format!("T{}", result);
+    }
+
+    result
+}
+
+pub fn to_field_name(src: &str) -> String {
+    let result = src.to_snake_case();
+
+    if result.is_empty() {
+        return "root".to_string();
+    }
+
+    if result.starts_with(|c: char| c.is_ascii_digit()) {
+        return format!("f_{}", result);
+    }
+
+    if RUST_KEYWORDS.contains(&result.as_str()) {
+        return format!("r#{}", result);
+    }
+
+    result
+}
+
+/// Every line of the multi-line description has to be the doc comment line
+fn doc_comment(description: &str, indent: &str) -> String {
+    let mut result = String::new();
+
+    for line in description.lines() {
+        result.push_str(indent);
+        result.push_str("///");
+
+        if !line.trim().is_empty() {
+            result.push(' ');
+            result.push_str(line.trim_end());
+        }
+
+        result.push('\n');
+    }
+
+    result
+}
+
+/// Converts JSON schemas into rust types. Object schemas become structs,
+/// `components/schemas` references become structs named after the schema
+pub struct RustTypes<'s> {
+    schemas: &'s serde_json::Map<String, Value>,
+    generated: HashSet<String>,
+    code: String,
+}
+
+impl<'s> RustTypes<'s> {
+    pub fn new(schemas: &'s serde_json::Map<String, Value>) -> Self {
+        Self {
+            schemas,
+            generated: HashSet::new(),
+            code: String::new(),
+        }
+    }
+
+    pub fn into_code(self) -> String {
+        self.code
+    }
+
+    /// Returns true if the name is not taken by the type generated before
+    pub fn reserve_name(&mut self, type_name: &str) -> bool {
+        self.generated.insert(type_name.to_string())
+    }
+
+    /// Returns rust type for the schema. `name_hint` is used for inline object schemas
+    pub fn resolve(&mut self, schema: &Value, name_hint: &str) -> Result<String, String> {
+        if let Some(reference) = schema.get("$ref").and_then(|itm| itm.as_str()) {
+            return self.resolve_ref(reference);
+        }
+
+        let rust_type = match schema.get("type").and_then(|itm| itm.as_str()) {
+            Some("string") => "String".to_string(),
+            Some("integer") => "i64".to_string(),
+            Some("number") => "f64".to_string(),
+            Some("boolean") => "bool".to_string(),
+            Some("array") => match schema.get("items") {
+                Some(items) => {
+                    let item_type = self.resolve(items, format!("{}Item", name_hint).as_str())?;
+                    format!("Vec<{}>", item_type)
+                }
+                None => "Vec<my_socket_io_client::serde_json::Value>".to_string(),
+            },
+            Some("object") | None if schema.get("properties").is_some() => {
+                self.generate_struct(name_hint, schema)?;
+                name_hint.to_string()
+            }
+            _ => "my_socket_io_client::serde_json::Value".to_string(),
+        };
+
+        if schema.get("nullable").and_then(|itm| itm.as_bool()) == Some(true) {
+            return Ok(format!("Option<{}>", rust_type));
+        }
+
+        Ok(rust_type)
+    }
+
+    fn resolve_ref(&mut self, reference: &str) -> Result<String, String> {
+        let Some(schema_name) = reference.strip_prefix("#/components/schemas/") else {
+            return Err(format!(
+                "Only '#/components/schemas/' references are supported in payloads. Reference: {}",
+                reference
+            ));
+        };
+
+        let schemas = self.schemas;
+
+        let schema = schemas
+            .get(schema_name)
+            .ok_or_else(|| format!("Schema {} is not found", schema_name))?;
+
+        let type_name = to_type_name(schema_name);
+
+        if self.generated.contains(type_name.as_str()) {
+            return Ok(type_name);
+        }
+
+        if schema.get("properties").is_none() {
+            let rust_type = self.resolve(schema, type_name.as_str())?;
+            self.generated.insert(type_name.clone());
+            self.code
+                .push_str(format!("pub type {} = {};\n\n", type_name, rust_type).as_str());
+            return Ok(type_name);
+        }
+
+        self.resolve(schema, type_name.as_str())
+    }
+
+    fn generate_struct(&mut self, type_name: &str, schema: &Value) -> Result<(), String> {
+        if !self.generated.insert(type_name.to_string()) {
+            return Err(format!("Type name {} is generated twice", type_name));
+        }
+
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(|itm| itm.as_array())
+            .map(|items| items.iter().filter_map(|itm| itm.as_str()).collect())
+            .unwrap_or_default();
+
+        let mut fields = String::new();
+
+        if let Some(properties) = schema.get("properties").and_then(|itm| itm.as_object()) {
+            for (property_name, property_schema) in properties {
+                let field_name = to_field_name(property_name);
+
+                let field_type = self.resolve(
+                    property_schema,
+                    format!("{}{}", type_name, to_type_name(property_name)).as_str(),
+                )?;
+
+                if let Some(description) = property_schema
+                    .get("description")
+                    .and_then(|itm| itm.as_str())
+                {
+                    fields.push_str(doc_comment(description, "    ").as_str());
+                }
+
+                if field_name.trim_start_matches("r#") != property_name {
+                    fields
+                        .push_str(format!("    #[serde(rename = {:?})]\n", property_name).as_str());
+                }
+
+                if required.contains(&property_name.as_str()) || field_type.starts_with("Option<") {
+                    fields.push_str(format!("    pub {}: {},\n", field_name, field_type).as_str());
+                } else {
+                    fields.push_str(
+                        "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n",
+                    );
+                    fields.push_str(
+                        format!("    pub {}: Option<{}>,\n", field_name, field_type).as_str(),
+                    );
+                }
+            }
+        }
+
+        if let Some(description) = schema.get("description").and_then(|itm| itm.as_str()) {
+            self.code.push_str(doc_comment(description, "").as_str());
+        }
+
+        self.code
+            .push_str("#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]\n");
+        self.code
+            .push_str(format!("pub struct {} {{\n{}}}\n\n", type_name, fields).as_str());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(schema: Value) -> String {
+        let schemas = serde_json::Map::new();
+        let mut types = RustTypes::new(&schemas);
+        types.resolve(&schema, "Model").unwrap();
+        types.into_code()
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(to_type_name("/brand-socket"), "BrandSocket");
+        assert_eq!(to_type_name("/"), "Root");
+        assert_eq!(to_type_name("1st"), "T1st");
+
+        assert_eq!(to_field_name("instrumentId"), "instrument_id");
+        assert_eq!(to_field_name("type"), "r#type");
+        assert_eq!(to_field_name("1st"), "f_1st");
+    }
+
+    #[test]
+    fn test_multi_line_description_is_doc_comment() {
+        let code = generate(serde_json::json!({
+            "type": "object",
+            "description": "First line\n\nThird line",
+            "properties": {
+                "id": { "type": "string", "description": "Id\nof the model" }
+            }
+        }));
+
+        assert!(code.contains("/// First line\n///\n/// Third line\n#[derive"));
+        assert!(code.contains("    /// Id\n    /// of the model\n"));
+    }
+
+    #[test]
+    fn test_rename_is_escaped() {
+        let code = generate(serde_json::json!({
+            "type": "object",
+            "required": ["a\"b\\c"],
+            "properties": {
+                "a\"b\\c": { "type": "string" }
+            }
+        }));
+
+        assert!(code.contains(r#"    #[serde(rename = "a\"b\\c")]"#));
+        assert!(code.contains("    pub a_b_c: String,\n"));
+    }
+
+    #[test]
+    fn test_not_required_fields_are_optional() {
+        let code = generate(serde_json::json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": {
+                "id": { "type": "integer" },
+                "price": { "type": "number" },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            }
+        }));
+
+        assert!(code.contains("    pub id: i64,\n"));
+        assert!(code.contains("    pub price: Option<f64>,\n"));
+        assert!(code.contains("    pub tags: Option<Vec<String>>,\n"));
+    }
+
+    #[test]
+    fn test_nested_objects_become_structs() {
+        let code = generate(serde_json::json!({
+            "type": "object",
+            "required": ["item"],
+            "properties": {
+                "item": { "type": "object", "properties": { "name": { "type": "string" } } }
+            }
+        }));
+
+        assert!(code.contains("pub struct ModelItem {"));
+        assert!(code.contains("    pub item: ModelItem,\n"));
+    }
+}
@@ -0,0 +1,204 @@
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationDirection {
+    /// Server emits the event, client subscribes to it
+    Subscribe,
+    /// Client emits the event to the server
+    Publish,
+}
+
+pub struct AsyncApiMessage {
+    pub event_name: String,
+    pub payload: Value,
+    pub ack: Option<Value>,
+}
+
+pub struct AsyncApiOperation {
+    pub direction: OperationDirection,
+    pub operation_id: Option<String>,
+    pub messages: Vec<AsyncApiMessage>,
+}
+
+pub struct AsyncApiChannel {
+    pub namespace: String,
+    pub operations: Vec<AsyncApiOperation>,
+}
+
+/// Socket.IO flavor of AsyncAPI 2.x document: channel is a namespace,
+/// message name is an event name and `x-ack` describes the ack payload
+pub struct AsyncApiSpec {
+    pub title: String,
+    pub channels: Vec<AsyncApiChannel>,
+    pub schemas: serde_json::Map<String, Value>,
+}
+
+impl AsyncApiSpec {
+    pub fn parse(src: &str) -> Result<Self, String> {
+        let document: Value = match serde_json::from_str(src) {
+            Ok(document) => document,
+            Err(_) => serde_yaml::from_str(src)
+                .map_err(|err| format!("Can not parse AsyncAPI document. Err: {}", err))?,
+        };
+
+        let version = document
+            .get("asyncapi")
+            .and_then(|itm| itm.as_str())
+            .ok_or("AsyncAPI document has no 'asyncapi' version field")?;
+
+        if !version.starts_with("2.") {
+            return Err(format!(
+                "Only AsyncAPI 2.x is supported. Document version: {}",
+                version
+            ));
+        }
+
+        let title = document
+            .pointer("/info/title")
+            .and_then(|itm| itm.as_str())
+            .unwrap_or("SocketIo")
+            .to_string();
+
+        let schemas = document
+            .pointer("/components/schemas")
+            .and_then(|itm| itm.as_object())
+            .cloned()
+            .unwrap_or_default();
+
+        let mut channels = Vec::new();
+
+        if let Some(items) = document.get("channels").and_then(|itm| itm.as_object()) {
+            for (namespace, channel) in items {
+                channels.push(parse_channel(&document, namespace, channel)?);
+            }
+        }
+
+        Ok(Self {
+            title,
+            channels,
+            schemas,
+        })
+    }
+}
+
+fn parse_channel(
+    document: &Value,
+    namespace: &str,
+    channel: &Value,
+) -> Result<AsyncApiChannel, String> {
+    let mut operations = Vec::new();
+
+    for (key, direction) in [
+        ("subscribe", OperationDirection::Subscribe),
+        ("publish", OperationDirection::Publish),
+    ] {
+        let Some(operation) = channel.get(key) else {
+            continue;
+        };
+
+        let operation_id = operation
+            .get("operationId")
+            .and_then(|itm| itm.as_str())
+            .map(|itm| itm.to_string());
+
+        let message = operation
+            .get("message")
+            .ok_or_else(|| format!("Operation {} of channel {} has no message", key, namespace))?;
+
+        let message = resolve_ref(document, message)?;
+
+        let mut messages = Vec::new();
+
+        match message.get("oneOf").and_then(|itm| itm.as_array()) {
+            Some(items) => {
+                for item in items {
+                    let item = resolve_ref(document, item)?;
+                    messages.push(parse_message(
+                        document,
+                        namespace,
+                        item,
+                        operation_id.as_deref(),
+                    )?);
+                }
+            }
+            None => {
+                messages.push(parse_message(
+                    document,
+                    namespace,
+                    message,
+                    operation_id.as_deref(),
+                )?);
+            }
+        }
+
+        operations.push(AsyncApiOperation {
+            direction,
+            operation_id,
+            messages,
+        });
+    }
+
+    Ok(AsyncApiChannel {
+        namespace: namespace.to_string(),
+        operations,
+    })
+}
+
+fn parse_message(
+    document: &Value,
+    namespace: &str,
+    message: &Value,
+    operation_id: Option<&str>,
+) -> Result<AsyncApiMessage, String> {
+    let event_name = message
+        .get("name")
+        .and_then(|itm| itm.as_str())
+        .or(operation_id)
+        .ok_or_else(|| {
+            format!(
+                "Message of channel {} has neither name nor operationId",
+                namespace
+            )
+        })?
+        .to_string();
+
+    let payload = match message.get("payload") {
+        Some(payload) => payload.clone(),
+        None => Value::Object(Default::default()),
+    };
+
+    let ack = match message.get("x-ack") {
+        Some(ack) => {
+            let ack = resolve_ref(document, ack)?;
+            Some(ack.get("args").unwrap_or(ack).clone())
+        }
+        None => None,
+    };
+
+    Ok(AsyncApiMessage {
+        event_name,
+        payload,
+        ack,
+    })
+}
+
+/// Resolves `$ref` pointing outside of `components/schemas`. Schema references are kept
+/// to become named types
+fn resolve_ref<'s>(document: &'s Value, value: &'s Value) -> Result<&'s Value, String> {
+    let Some(reference) = value.get("$ref").and_then(|itm| itm.as_str()) else {
+        return Ok(value);
+    };
+
+    let Some(pointer) = reference.strip_prefix('#') else {
+        return Err(format!(
+            "Only local references are supported. Reference: {}",
+            reference
+        ));
+    };
+
+    let resolved = document
+        .pointer(pointer)
+        .ok_or_else(|| format!("Can not resolve reference {}", reference))?;
+
+    resolve_ref(document, resolved)
+}
@@ -0,0 +1,226 @@
+// Generated by my-socket-io-client-codegen. Do not edit.
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RootNewsPayloadItemsItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RootNewsPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec<RootNewsPayloadItemsItem>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+}
+
+/// Price tick.
+/// Sent on every quote change
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Price {
+    pub ask: Option<f64>,
+    pub bid: f64,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BrandSocketStreamAckPayload {
+    pub ok: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BrandSocketPlaceOrderPayload {
+    /// Instrument of the order.
+    ///
+    /// Has to be active
+    #[serde(rename = "instrumentId")]
+    pub instrument_id: String,
+    #[serde(rename = "tag\"name\\path")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_name_path: Option<String>,
+    pub volume: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BrandSocketPlaceOrderAckPayload {
+    #[serde(rename = "orderId")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct RootNewsEvent(pub RootNewsPayload);
+
+impl my_socket_io_client::SocketIoSubscribeEventModel for RootNewsEvent {
+    const NAME_SPACE: &'static str = "/";
+    const EVENT_NAME: &'static str = "news";
+
+    fn deserialize(payload: &str) -> Self {
+        match <Self as my_socket_io_client::SocketIoSubscribeEventModel>::try_deserialize(payload) {
+            Ok(result) => result,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_deserialize(payload: &str) -> Result<Self, String> {
+        my_socket_io_client::serde_json::from_str(payload).map_err(|err| {
+            format!("Can not deserialize RootNewsEvent. Payload: '{}'. Err: {}", payload, err)
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct BrandSocketStreamAck(pub BrandSocketStreamAckPayload);
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct BrandSocketStreamEvent(pub Price);
+
+impl my_socket_io_client::SocketIoSubscribeEventModel for BrandSocketStreamEvent {
+    const NAME_SPACE: &'static str = "/brand-socket";
+    const EVENT_NAME: &'static str = "stream";
+
+    fn deserialize(payload: &str) -> Self {
+        match <Self as my_socket_io_client::SocketIoSubscribeEventModel>::try_deserialize(payload) {
+            Ok(result) => result,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_deserialize(payload: &str) -> Result<Self, String> {
+        my_socket_io_client::serde_json::from_str(payload).map_err(|err| {
+            format!("Can not deserialize BrandSocketStreamEvent. Payload: '{}'. Err: {}", payload, err)
+        })
+    }
+}
+
+impl my_socket_io_client::SocketIoSubscribeOutModel for BrandSocketStreamAck {
+    fn serialize(&self) -> String {
+        match <Self as my_socket_io_client::SocketIoSubscribeOutModel>::try_serialize(self) {
+            Ok(result) => result,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_serialize(&self) -> Result<String, String> {
+        my_socket_io_client::serde_json::to_string(self)
+            .map_err(|err| format!("Can not serialize BrandSocketStreamAck. Err: {}", err))
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct BrandSocketPlaceOrderAck(pub BrandSocketPlaceOrderAckPayload);
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct BrandSocketPlaceOrderRequest(pub BrandSocketPlaceOrderPayload);
+
+impl my_socket_io_client::SocketIoRpcInModel for BrandSocketPlaceOrderRequest {
+    const NAME_SPACE: &'static str = "/brand-socket";
+    const EVENT_NAME: &'static str = "placeOrder";
+
+    fn serialize(&self) -> String {
+        match <Self as my_socket_io_client::SocketIoRpcInModel>::try_serialize(self) {
+            Ok(result) => result,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_serialize(&self) -> Result<String, String> {
+        my_socket_io_client::serde_json::to_string(self)
+            .map_err(|err| format!("Can not serialize BrandSocketPlaceOrderRequest. Err: {}", err))
+    }
+}
+
+impl my_socket_io_client::SocketIoRpcOutModel for BrandSocketPlaceOrderAck {
+    fn deserialize(payload: &str) -> Self {
+        match <Self as my_socket_io_client::SocketIoRpcOutModel>::try_deserialize(payload) {
+            Ok(result) => result,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_deserialize(payload: &str) -> Result<Self, String> {
+        my_socket_io_client::serde_json::from_str(payload).map_err(|err| {
+            format!("Can not deserialize BrandSocketPlaceOrderAck. Payload: '{}'. Err: {}", payload, err)
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct BrandSocketPingRequest(pub String);
+
+impl my_socket_io_client::SocketIoRpcInModel for BrandSocketPingRequest {
+    const NAME_SPACE: &'static str = "/brand-socket";
+    const EVENT_NAME: &'static str = "ping";
+
+    fn serialize(&self) -> String {
+        match <Self as my_socket_io_client::SocketIoRpcInModel>::try_serialize(self) {
+            Ok(result) => result,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_serialize(&self) -> Result<String, String> {
+        my_socket_io_client::serde_json::to_string(self)
+            .map_err(|err| format!("Can not serialize BrandSocketPingRequest. Err: {}", err))
+    }
+}
+
+pub struct BrandSocketSocketIoClient {
+    client: std::sync::Arc<my_socket_io_client::MySocketIoClient>,
+}
+
+impl BrandSocketSocketIoClient {
+    pub fn new(client: std::sync::Arc<my_socket_io_client::MySocketIoClient>) -> Self {
+        Self { client }
+    }
+
+    pub fn get_client(&self) -> &std::sync::Arc<my_socket_io_client::MySocketIoClient> {
+        &self.client
+    }
+
+    pub async fn on_news(
+        &self,
+        callbacks: std::sync::Arc<
+            dyn my_socket_io_client::SocketIoEventSubscriberCallback<RootNewsEvent, ()>
+                + Send
+                + Sync
+                + 'static,
+        >,
+    ) -> my_socket_io_client::SocketIoSubscription {
+        self.client.register_subscriber(callbacks).await
+    }
+
+    pub async fn on_stream(
+        &self,
+        callbacks: std::sync::Arc<
+            dyn my_socket_io_client::SocketIoEventSubscriberCallback<BrandSocketStreamEvent, BrandSocketStreamAck>
+                + Send
+                + Sync
+                + 'static,
+        >,
+    ) -> my_socket_io_client::SocketIoSubscription {
+        self.client.register_subscriber(callbacks).await
+    }
+
+    pub async fn emit_brand_socket_place_order(&self, model: &BrandSocketPlaceOrderRequest) -> Result<BrandSocketPlaceOrderAck, String> {
+        let Some(connection) = self.client.get_connection().await else {
+            return Err("Socket is not connected".to_string());
+        };
+
+        connection.send_event_with_ack(model).await
+    }
+
+    pub async fn emit_brand_socket_ping(&self, model: &BrandSocketPingRequest) -> Result<(), String> {
+        let Some(connection) = self.client.get_connection().await else {
+            return Err("Socket is not connected".to_string());
+        };
+
+        connection.try_send_event_and_forget(model).await
+    }
+}
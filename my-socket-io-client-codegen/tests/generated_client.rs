@@ -0,0 +1,42 @@
+#[allow(dead_code)]
+mod brand_socket {
+    include!("fixtures/brand_socket_client.rs");
+}
+
+use brand_socket::*;
+use my_socket_io_client::{SocketIoRpcInModel, SocketIoSubscribeEventModel};
+
+const SPEC: &str = include_str!("fixtures/brand_socket.yaml");
+
+/// Fixture is compiled as the part of the test, so the generated code has to stay compilable
+#[test]
+fn test_generated_code_matches_fixture() {
+    let code = my_socket_io_client_codegen::generate(SPEC).unwrap();
+    assert_eq!(code, include_str!("fixtures/brand_socket_client.rs"));
+}
+
+#[test]
+fn test_generated_event_model() {
+    assert_eq!(BrandSocketStreamEvent::NAME_SPACE, "/brand-socket");
+    assert_eq!(BrandSocketStreamEvent::EVENT_NAME, "stream");
+
+    let event = BrandSocketStreamEvent::try_deserialize(r#"{"id":"EURUSD","bid":1.1}"#).unwrap();
+    assert_eq!(event.0.id, "EURUSD");
+    assert_eq!(event.0.ask, None);
+
+    assert!(BrandSocketStreamEvent::try_deserialize(r#"{"id":"EURUSD"}"#).is_err());
+}
+
+#[test]
+fn test_generated_renamed_fields() {
+    let request = BrandSocketPlaceOrderRequest(BrandSocketPlaceOrderPayload {
+        instrument_id: "EURUSD".to_string(),
+        tag_name_path: Some("tag".to_string()),
+        volume: 1.0,
+    });
+
+    assert_eq!(
+        request.try_serialize().unwrap(),
+        r#"{"instrumentId":"EURUSD","tag\"name\\path":"tag","volume":1.0}"#
+    );
+}
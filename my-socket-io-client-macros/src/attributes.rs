@@ -0,0 +1,47 @@
+use syn::{DeriveInput, LitStr};
+
+#[derive(Default)]
+pub struct SocketIoAttributes {
+    pub namespace: Option<LitStr>,
+    pub event: Option<LitStr>,
+}
+
+impl SocketIoAttributes {
+    pub fn parse(input: &DeriveInput) -> Result<Self, syn::Error> {
+        let mut result = Self::default();
+
+        for attr in input.attrs.iter() {
+            if !attr.path().is_ident("socket_io") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("namespace") {
+                    result.namespace = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("event") {
+                    result.event = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                Err(meta.error("Supported attributes are: namespace, event"))
+            })?;
+        }
+
+        Ok(result)
+    }
+
+    /// Both namespace and event have to be set or none of them
+    pub fn get_names(&self, input: &DeriveInput) -> Result<Option<(LitStr, LitStr)>, syn::Error> {
+        match (&self.namespace, &self.event) {
+            (Some(namespace), Some(event)) => Ok(Some((namespace.clone(), event.clone()))),
+            (None, None) => Ok(None),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                "#[socket_io(namespace = \"...\", event = \"...\")] requires both namespace and event",
+            )),
+        }
+    }
+}
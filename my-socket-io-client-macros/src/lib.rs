@@ -0,0 +1,330 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+mod attributes;
+use attributes::*;
+
+/// Implements `SocketIoSubscribeEventModel` with serde_json based deserialization.
+/// Without `#[socket_io(namespace = "...", event = "...")]` implements `SocketIoSubscribePayloadModel`
+#[proc_macro_derive(SocketIoEvent, attributes(socket_io))]
+pub fn socket_io_event(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    match generate_socket_io_event(&input) {
+        Ok(result) => result.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Implements `SocketIoSubscribeOutModel` with serde_json based serialization
+#[proc_macro_derive(SocketIoOut)]
+pub fn socket_io_out(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    generate_socket_io_out(&input).into()
+}
+
+/// Implements `SocketIoRpcInModel` with serde_json based serialization.
+/// Without `#[socket_io(namespace = "...", event = "...")]` implements `SocketIoRpcPayloadModel`
+#[proc_macro_derive(SocketIoRpcIn, attributes(socket_io))]
+pub fn socket_io_rpc_in(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    match generate_socket_io_rpc_in(&input) {
+        Ok(result) => result.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Implements `SocketIoRpcOutModel` with serde_json based deserialization
+#[proc_macro_derive(SocketIoRpcOut)]
+pub fn socket_io_rpc_out(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    generate_socket_io_rpc_out(&input).into()
+}
+
+/// Generic models are serialized only if their generic arguments are serializable
+fn with_self_bound(input: &DeriveInput, bound: proc_macro2::TokenStream) -> syn::Generics {
+    let mut generics = input.generics.clone();
+
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(Self: #bound));
+
+    generics
+}
+
+fn deserialize_body(
+    name: &syn::Ident,
+    trait_path: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        fn deserialize(payload: &str) -> Self {
+            match <Self as #trait_path>::try_deserialize(payload) {
+                Ok(result) => result,
+                Err(err) => panic!("{}", err),
+            }
+        }
+
+        fn try_deserialize(payload: &str) -> Result<Self, String> {
+            my_socket_io_client::serde_json::from_str(payload).map_err(|err| {
+                format!("Can not deserialize {}. Payload: '{}'. Err: {}", stringify!(#name), payload, err)
+            })
+        }
+    }
+}
+
+fn serialize_body(
+    name: &syn::Ident,
+    trait_path: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        fn serialize(&self) -> String {
+            match <Self as #trait_path>::try_serialize(self) {
+                Ok(result) => result,
+                Err(err) => panic!("{}", err),
+            }
+        }
+
+        fn try_serialize(&self) -> Result<String, String> {
+            my_socket_io_client::serde_json::to_string(self)
+                .map_err(|err| format!("Can not serialize {}. Err: {}", stringify!(#name), err))
+        }
+    }
+}
+
+fn generate_socket_io_event(input: &DeriveInput) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let name = &input.ident;
+    let generics = with_self_bound(
+        input,
+        quote!(my_socket_io_client::serde::de::DeserializeOwned),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let names = SocketIoAttributes::parse(input)?.get_names(input)?;
+
+    let trait_impl = match names {
+        Some((namespace, event)) => {
+            let body = deserialize_body(
+                name,
+                quote!(my_socket_io_client::SocketIoSubscribeEventModel),
+            );
+
+            quote! {
+                impl #impl_generics my_socket_io_client::SocketIoSubscribeEventModel for #name #ty_generics #where_clause {
+                    const NAME_SPACE: &'static str = #namespace;
+                    const EVENT_NAME: &'static str = #event;
+                    #body
+                }
+            }
+        }
+        None => {
+            let body = deserialize_body(
+                name,
+                quote!(my_socket_io_client::SocketIoSubscribePayloadModel),
+            );
+
+            quote! {
+                impl #impl_generics my_socket_io_client::SocketIoSubscribePayloadModel for #name #ty_generics #where_clause {
+                    #body
+                }
+            }
+        }
+    };
+
+    Ok(trait_impl)
+}
+
+fn generate_socket_io_out(input: &DeriveInput) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let generics = with_self_bound(input, quote!(my_socket_io_client::serde::Serialize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = serialize_body(name, quote!(my_socket_io_client::SocketIoSubscribeOutModel));
+
+    quote! {
+        impl #impl_generics my_socket_io_client::SocketIoSubscribeOutModel for #name #ty_generics #where_clause {
+            #body
+        }
+    }
+}
+
+fn generate_socket_io_rpc_in(input: &DeriveInput) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let name = &input.ident;
+    let generics = with_self_bound(input, quote!(my_socket_io_client::serde::Serialize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let names = SocketIoAttributes::parse(input)?.get_names(input)?;
+
+    let trait_impl = match names {
+        Some((namespace, event)) => {
+            let body = serialize_body(name, quote!(my_socket_io_client::SocketIoRpcInModel));
+
+            quote! {
+                impl #impl_generics my_socket_io_client::SocketIoRpcInModel for #name #ty_generics #where_clause {
+                    const NAME_SPACE: &'static str = #namespace;
+                    const EVENT_NAME: &'static str = #event;
+                    #body
+                }
+            }
+        }
+        None => {
+            let body = serialize_body(name, quote!(my_socket_io_client::SocketIoRpcPayloadModel));
+
+            quote! {
+                impl #impl_generics my_socket_io_client::SocketIoRpcPayloadModel for #name #ty_generics #where_clause {
+                    #body
+                }
+            }
+        }
+    };
+
+    Ok(trait_impl)
+}
+
+fn generate_socket_io_rpc_out(input: &DeriveInput) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let generics = with_self_bound(
+        input,
+        quote!(my_socket_io_client::serde::de::DeserializeOwned),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = deserialize_body(name, quote!(my_socket_io_client::SocketIoRpcOutModel));
+
+    quote! {
+        impl #impl_generics my_socket_io_client::SocketIoRpcOutModel for #name #ty_generics #where_clause {
+            #body
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(
+        generate: impl Fn(&DeriveInput) -> Result<proc_macro2::TokenStream, syn::Error>,
+        input: DeriveInput,
+    ) -> String {
+        generate(&input).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_event_with_names_implements_event_model() {
+        let result = expand(
+            generate_socket_io_event,
+            syn::parse_quote! {
+                #[socket_io(namespace = "/brand-socket", event = "stream")]
+                struct StreamModel {
+                    name: String,
+                }
+            },
+        );
+
+        assert!(
+            result.contains("my_socket_io_client :: SocketIoSubscribeEventModel for StreamModel")
+        );
+        assert!(result.contains("const NAME_SPACE : & 'static str = \"/brand-socket\""));
+        assert!(result.contains("const EVENT_NAME : & 'static str = \"stream\""));
+        assert!(result.contains("fn try_deserialize (payload : & str) -> Result < Self , String >"));
+    }
+
+    #[test]
+    fn test_event_without_names_implements_payload_model() {
+        let result = expand(
+            generate_socket_io_event,
+            syn::parse_quote! {
+                struct StreamModel {
+                    name: String,
+                }
+            },
+        );
+
+        assert!(
+            result.contains("my_socket_io_client :: SocketIoSubscribePayloadModel for StreamModel")
+        );
+        assert!(!result.contains("NAME_SPACE"));
+        assert!(result.contains("fn try_deserialize"));
+    }
+
+    #[test]
+    fn test_rpc_in_implements_try_serialize() {
+        let result = expand(
+            generate_socket_io_rpc_in,
+            syn::parse_quote! {
+                #[socket_io(namespace = "/brand-socket", event = "command")]
+                struct CommandModel {
+                    id: String,
+                }
+            },
+        );
+
+        assert!(result.contains("my_socket_io_client :: SocketIoRpcInModel for CommandModel"));
+        assert!(result.contains("fn try_serialize (& self) -> Result < String , String >"));
+    }
+
+    #[test]
+    fn test_out_models_implement_try_methods() {
+        let input: DeriveInput = syn::parse_quote! {
+            struct ResultModel {
+                ok: bool,
+            }
+        };
+
+        let result = generate_socket_io_out(&input).to_string();
+        assert!(result.contains("my_socket_io_client :: SocketIoSubscribeOutModel for ResultModel"));
+        assert!(result.contains("fn try_serialize"));
+
+        let result = generate_socket_io_rpc_out(&input).to_string();
+        assert!(result.contains("my_socket_io_client :: SocketIoRpcOutModel for ResultModel"));
+        assert!(result.contains("fn try_deserialize"));
+    }
+
+    #[test]
+    fn test_generic_model_requires_serde_bound() {
+        let result = expand(
+            generate_socket_io_event,
+            syn::parse_quote! {
+                struct Wrapper<T> {
+                    value: T,
+                }
+            },
+        );
+
+        assert!(result.contains(
+            "for Wrapper < T > where Self : my_socket_io_client :: serde :: de :: DeserializeOwned"
+        ));
+    }
+
+    #[test]
+    fn test_namespace_without_event_is_error() {
+        let input: DeriveInput = syn::parse_quote! {
+            #[socket_io(namespace = "/brand-socket")]
+            struct StreamModel {
+                name: String,
+            }
+        };
+
+        let err = generate_socket_io_event(&input).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("requires both namespace and event"));
+    }
+
+    #[test]
+    fn test_unknown_attribute_is_error() {
+        let input: DeriveInput = syn::parse_quote! {
+            #[socket_io(namespace = "/brand-socket", event = "stream", ack = "x")]
+            struct StreamModel {
+                name: String,
+            }
+        };
+
+        let err = generate_socket_io_rpc_in(&input).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Supported attributes are: namespace, event"));
+    }
+}
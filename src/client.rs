@@ -1,8 +1,9 @@
-use std::sync::Arc;
+use std::{hash::Hash, sync::Arc, time::Duration};
 
 use my_web_socket_client::hyper_tungstenite::tungstenite::Message;
 use my_web_socket_client::*;
 use rust_extensions::{Logger, StrOrString};
+use socket_io_utils::SocketIoMessage;
 
 use crate::{client_inner::*, *};
 
@@ -41,11 +42,84 @@ impl MySocketIoClient {
         self
     }
 
+    /// Sends namespace DISCONNECT when the last subscriber of the namespace is unregistered
+    pub fn set_disconnect_unused_namespaces(self, value: bool) -> Self {
+        self.inner
+            .disconnect_unused_namespaces
+            .store(value, std::sync::atomic::Ordering::Relaxed);
+        self
+    }
+
+    /// Execution mode of the subscriber handlers if it is not specified at the registration.
+    /// Default is [`SocketIoExecutionMode::Inline`]. Keyed, batch and conflating subscribers
+    /// run on their own workers and are not affected by the mode
+    pub fn set_default_execution_mode(self, mode: SocketIoExecutionMode) -> Self {
+        *self.inner.default_execution_mode.lock().unwrap() = mode;
+        self
+    }
+
+    /// Validates payloads against the JSON schemas registered in the validator
+    #[cfg(feature = "json-schema")]
+    pub fn set_schema_validator(self, schema_validator: Arc<SocketIoSchemaValidator>) -> Self {
+        *self.inner.schema_validator.lock().unwrap() = Some(schema_validator);
+        self
+    }
+
+    /// Bounded queue between the socket read loop and the handlers of all the namespaces
+    /// which have no queue of their own
+    pub fn set_inbound_queue(self, settings: SocketIoInboundQueueSettings) -> Self {
+        self.inner.set_inbound_queue(None, settings);
+        self
+    }
+
+    pub fn set_namespace_inbound_queue(
+        self,
+        namespace: impl Into<StrOrString<'static>>,
+        settings: SocketIoInboundQueueSettings,
+    ) -> Self {
+        let namespace: StrOrString<'static> = namespace.into();
+        self.inner
+            .set_inbound_queue(Some(namespace.as_str().to_string()), settings);
+        self
+    }
+
+    pub fn get_inbound_queue_metrics(&self) -> Option<Arc<SocketIoInboundQueueMetrics>> {
+        self.inner.get_inbound_queue_metrics(None)
+    }
+
+    pub fn get_namespace_inbound_queue_metrics(
+        &self,
+        namespace: &str,
+    ) -> Option<Arc<SocketIoInboundQueueMetrics>> {
+        self.inner.get_inbound_queue_metrics(Some(namespace))
+    }
+
+    /// Receives events which handlers panicked. Panics are written to the logger anyway
+    pub fn set_dead_letter_callback(
+        self,
+        callback: Arc<dyn SocketIoDeadLetterCallback + Send + Sync + 'static>,
+    ) -> Self {
+        self.inner
+            .get_panic_reporter()
+            .set_dead_letter_callback(callback);
+        self
+    }
+
+    /// Calling it again on the started client does nothing
     pub fn start(&self) {
+        if !self.inner.start() {
+            return;
+        }
+
         let ping_message = Message::Ping(bytes::Bytes::new());
         self.ws_client.start(Some(ping_message), self.inner.clone());
     }
 
+    /// Returns current connection if the handshake is done
+    pub async fn get_connection(&self) -> Option<Arc<SocketIoConnection>> {
+        self.inner.get_handshaked_connection().await
+    }
+
     pub async fn register_subscriber<
         TModel: SocketIoSubscribeEventModel + Send + Sync + 'static,
         TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static + serde::Serialize,
@@ -54,12 +128,738 @@ impl MySocketIoClient {
         callbacks: Arc<
             dyn SocketIoEventSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
         >,
-    ) {
+    ) -> SocketIoSubscription {
+        self.register_subscriber_to(TModel::NAME_SPACE, TModel::EVENT_NAME, callbacks)
+            .await
+    }
+
+    pub async fn register_subscriber_to<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    >(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        callbacks: Arc<
+            dyn SocketIoEventSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        self.register_subscriber_with_mode_to(
+            namespace,
+            event_name,
+            self.inner.get_default_execution_mode(),
+            callbacks,
+        )
+        .await
+    }
+
+    /// Same as [`MySocketIoClient::register_subscriber`] with the execution mode of the handler
+    /// overriding the default one
+    pub async fn register_subscriber_with_mode<
+        TModel: SocketIoSubscribeEventModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    >(
+        &self,
+        mode: SocketIoExecutionMode,
+        callbacks: Arc<
+            dyn SocketIoEventSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        self.register_subscriber_with_mode_to(
+            TModel::NAME_SPACE,
+            TModel::EVENT_NAME,
+            mode,
+            callbacks,
+        )
+        .await
+    }
+
+    pub async fn register_subscriber_with_mode_to<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    >(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        mode: SocketIoExecutionMode,
+        callbacks: Arc<
+            dyn SocketIoEventSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
         let subscriber = SocketIoEventSubscriber { callbacks };
+        let subscriber =
+            SocketIoExecutor::create(mode, Arc::new(subscriber), self.inner.get_panic_reporter());
+        let id = self
+            .inner
+            .register_subscriber(
+                namespace.as_str().to_string(),
+                SocketIoEventNamePattern::exact(event_name.as_str()),
+                subscriber,
+            )
+            .await;
+
+        SocketIoSubscription::new(id, &self.inner)
+    }
+
+    /// Events with the same key are processed in order, different keys are processed in parallel
+    /// on `workers_amount` tasks
+    pub async fn register_keyed_subscriber<
+        TModel: SocketIoSubscribeEventModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+        TKey: Hash,
+    >(
+        &self,
+        workers_amount: usize,
+        key_extractor: impl Fn(&TModel) -> TKey + Send + Sync + 'static,
+        callbacks: Arc<
+            dyn SocketIoEventSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        self.register_keyed_subscriber_to(
+            TModel::NAME_SPACE,
+            TModel::EVENT_NAME,
+            workers_amount,
+            key_extractor,
+            callbacks,
+        )
+        .await
+    }
+
+    pub async fn register_keyed_subscriber_to<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+        TKey: Hash,
+    >(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        workers_amount: usize,
+        key_extractor: impl Fn(&TModel) -> TKey + Send + Sync + 'static,
+        callbacks: Arc<
+            dyn SocketIoEventSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let subscriber = SocketIoKeyedSubscriber::new(
+            workers_amount,
+            key_extractor,
+            callbacks,
+            self.inner.get_panic_reporter(),
+        );
         let subscriber = Arc::new(subscriber);
-        self.inner
+        let id = self
+            .inner
+            .register_subscriber(
+                namespace.as_str().to_string(),
+                SocketIoEventNamePattern::exact(event_name.as_str()),
+                subscriber,
+            )
+            .await;
+
+        SocketIoSubscription::new(id, &self.inner)
+    }
+
+    /// Handler receives events in batches of up to `max_batch_size` events
+    /// or the events accumulated during `max_delay`
+    pub async fn register_batch_subscriber<
+        TModel: SocketIoSubscribeEventModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    >(
+        &self,
+        max_batch_size: usize,
+        max_delay: Duration,
+        callbacks: Arc<
+            dyn SocketIoBatchSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        self.register_batch_subscriber_to(
+            TModel::NAME_SPACE,
+            TModel::EVENT_NAME,
+            max_batch_size,
+            max_delay,
+            callbacks,
+        )
+        .await
+    }
+
+    pub async fn register_batch_subscriber_to<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    >(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        max_batch_size: usize,
+        max_delay: Duration,
+        callbacks: Arc<
+            dyn SocketIoBatchSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let subscriber = SocketIoBatchSubscriber::new(
+            max_batch_size,
+            max_delay,
+            callbacks,
+            self.inner.get_panic_reporter(),
+        );
+        let subscriber = Arc::new(subscriber);
+        let id = self
+            .inner
+            .register_subscriber(
+                namespace.as_str().to_string(),
+                SocketIoEventNamePattern::exact(event_name.as_str()),
+                subscriber,
+            )
+            .await;
+
+        SocketIoSubscription::new(id, &self.inner)
+    }
+
+    /// Keeps only the latest event per key while the handler is busy.
+    /// Returned metrics count the events replaced by the newer ones
+    pub async fn register_conflating_subscriber<
+        TModel: SocketIoSubscribeEventModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+        TKey: Hash + Eq + Clone + Send + 'static,
+    >(
+        &self,
+        key_extractor: impl Fn(&TModel) -> TKey + Send + Sync + 'static,
+        callbacks: Arc<
+            dyn SocketIoEventSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> (SocketIoSubscription, Arc<SocketIoConflationMetrics>) {
+        self.register_conflating_subscriber_to(
+            TModel::NAME_SPACE,
+            TModel::EVENT_NAME,
+            key_extractor,
+            callbacks,
+        )
+        .await
+    }
+
+    pub async fn register_conflating_subscriber_to<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+        TKey: Hash + Eq + Clone + Send + 'static,
+    >(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        key_extractor: impl Fn(&TModel) -> TKey + Send + Sync + 'static,
+        callbacks: Arc<
+            dyn SocketIoEventSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> (SocketIoSubscription, Arc<SocketIoConflationMetrics>) {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let subscriber = SocketIoConflatingSubscriber::new(
+            key_extractor,
+            callbacks,
+            self.inner.get_panic_reporter(),
+        );
+        let metrics = subscriber.get_metrics();
+        let subscriber = Arc::new(subscriber);
+        let id = self
+            .inner
+            .register_subscriber(
+                namespace.as_str().to_string(),
+                SocketIoEventNamePattern::exact(event_name.as_str()),
+                subscriber,
+            )
+            .await;
+
+        (SocketIoSubscription::new(id, &self.inner), metrics)
+    }
+
+    /// Subscriber receives [`SocketIoEventContext`] with the event metadata and the connection to reply
+    pub async fn register_context_subscriber<
+        TModel: SocketIoSubscribeEventModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    >(
+        &self,
+        callbacks: Arc<
+            dyn SocketIoContextSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        self.register_context_subscriber_to(TModel::NAME_SPACE, TModel::EVENT_NAME, callbacks)
+            .await
+    }
+
+    pub async fn register_context_subscriber_to<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    >(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        callbacks: Arc<
+            dyn SocketIoContextSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let subscriber = SocketIoContextSubscriber { callbacks };
+        let subscriber = SocketIoExecutor::create(
+            self.inner.get_default_execution_mode(),
+            Arc::new(subscriber),
+            self.inner.get_panic_reporter(),
+        );
+        let id = self
+            .inner
+            .register_subscriber(
+                namespace.as_str().to_string(),
+                SocketIoEventNamePattern::exact(event_name.as_str()),
+                subscriber,
+            )
+            .await;
+
+        SocketIoSubscription::new(id, &self.inner)
+    }
+
+    /// Subscriber receives [`SocketIoAckSender`] and can answer the ack later from any task
+    pub async fn register_deferred_ack_subscriber<
+        TModel: SocketIoSubscribeEventModel + Send + Sync + 'static,
+    >(
+        &self,
+        callbacks: Arc<dyn SocketIoDeferredAckSubscriberCallback<TModel> + Send + Sync + 'static>,
+    ) -> SocketIoSubscription {
+        self.register_deferred_ack_subscriber_to(TModel::NAME_SPACE, TModel::EVENT_NAME, callbacks)
+            .await
+    }
+
+    pub async fn register_deferred_ack_subscriber_to<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+    >(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        callbacks: Arc<dyn SocketIoDeferredAckSubscriberCallback<TModel> + Send + Sync + 'static>,
+    ) -> SocketIoSubscription {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let subscriber = SocketIoDeferredAckSubscriber { callbacks };
+        let subscriber = SocketIoExecutor::create(
+            self.inner.get_default_execution_mode(),
+            Arc::new(subscriber),
+            self.inner.get_panic_reporter(),
+        );
+        let id = self
+            .inner
+            .register_subscriber(
+                namespace.as_str().to_string(),
+                SocketIoEventNamePattern::exact(event_name.as_str()),
+                subscriber,
+            )
+            .await;
+
+        SocketIoSubscription::new(id, &self.inner)
+    }
+
+    /// Closure based subscriber. Payload is deserialized and the result is serialized with serde_json
+    /// ```ignore
+    /// client
+    ///     .on::<StreamModel>("/brand-socket", "stream", |payload: StreamModel| async move {
+    ///         println!("{:?}", payload);
+    ///     })
+    ///     .await;
+    /// ```
+    pub async fn on<TModel: serde::de::DeserializeOwned + Send + 'static>(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        handler: impl SocketIoEventHandlerFn<TModel>,
+    ) -> SocketIoSubscription {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let subscriber = SocketIoClosureSubscriber::new(handler, self.inner.get_logger());
+        let subscriber = SocketIoExecutor::create(
+            self.inner.get_default_execution_mode(),
+            Arc::new(subscriber),
+            self.inner.get_panic_reporter(),
+        );
+        let id = self
+            .inner
+            .register_subscriber(
+                namespace.as_str().to_string(),
+                SocketIoEventNamePattern::exact(event_name.as_str()),
+                subscriber,
+            )
+            .await;
+
+        SocketIoSubscription::new(id, &self.inner)
+    }
+
+    /// Delivers events through a bounded buffer. If consumer falls behind - events are dropped
+    /// and [`SocketIoStreamRecvError::Lagged`] is returned by the stream.
+    /// Stream only observes the event: it does not answer the ack and does not take the event from the subscribers
+    pub async fn subscribe_stream<TModel: SocketIoSubscribeEventModel + Send + 'static>(
+        &self,
+        buffer_size: usize,
+    ) -> SocketIoEventStream<TModel> {
+        self.subscribe_stream_to(TModel::NAME_SPACE, TModel::EVENT_NAME, buffer_size)
+            .await
+    }
+
+    pub async fn subscribe_stream_to<TModel: SocketIoSubscribePayloadModel + Send + 'static>(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        buffer_size: usize,
+    ) -> SocketIoEventStream<TModel> {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let (observer, mut stream) = SocketIoStreamObserver::new(buffer_size);
+        let id = self
+            .inner
+            .register_observer(
+                namespace.as_str().to_string(),
+                event_name.as_str().to_string(),
+                Arc::new(observer),
+            )
+            .await;
+
+        stream.set_subscription(SocketIoSubscription::new(id, &self.inner));
+        stream
+    }
+
+    /// Registers temporary listener which resolves with the first event matching the predicate.
+    /// Call it before emitting the command and await [`SocketIoEventWaiter::get_result`] after.
+    /// Listener does not answer the ack and does not change which subscriber receives the event
+    pub async fn wait_for_event<TModel: SocketIoSubscribeEventModel + Send + 'static>(
+        &self,
+        predicate: impl Fn(&TModel) -> bool + Send + Sync + 'static,
+        timeout: Duration,
+    ) -> SocketIoEventWaiter<TModel> {
+        self.wait_for_event_from(TModel::NAME_SPACE, TModel::EVENT_NAME, predicate, timeout)
+            .await
+    }
+
+    pub async fn wait_for_event_from<TModel: SocketIoSubscribePayloadModel + Send + 'static>(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        predicate: impl Fn(&TModel) -> bool + Send + Sync + 'static,
+        timeout: Duration,
+    ) -> SocketIoEventWaiter<TModel> {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let (observer, mut waiter) = SocketIoEventWaiterObserver::new(predicate, timeout);
+        let id = self
+            .inner
+            .register_observer(
+                namespace.as_str().to_string(),
+                event_name.as_str().to_string(),
+                Arc::new(observer),
+            )
+            .await;
+
+        waiter.set_subscription(SocketIoSubscription::new(id, &self.inner));
+
+        if let Some(connection) = self.inner.get_handshaked_connection().await {
+            waiter.set_connection(connection);
+        }
+
+        waiter
+    }
+
+    /// Emits the request and waits for the response event carrying the same correlation id.
+    /// Fails if the timeout is reached or the socket is disconnected before the response.
+    /// Response is only observed, so subscribers of the response event still receive it and answer its ack
+    pub async fn send_event_with_response<
+        TInModel: SocketIoCorrelatedRpcInModel,
+        TOutModel: SocketIoRpcOutModel,
+    >(
+        &self,
+        data: &TInModel,
+        timeout: Duration,
+    ) -> Result<TOutModel, String> {
+        let Some(connection) = self.inner.get_handshaked_connection().await else {
+            return Err("Socket is not connected".to_string());
+        };
+
+        let correlation_id = format!(
+            "{}-{}",
+            connection.get_sid().await.unwrap_or_default(),
+            self.inner.get_next_correlation_id()
+        );
+
+        let payload = inject_correlation_id(
+            data.try_serialize()?.as_str(),
+            TInModel::CORRELATION_ID_FIELD,
+            correlation_id.as_str(),
+        )?;
+
+        connection.check_outgoing_payload(
+            TInModel::NAME_SPACE,
+            TInModel::EVENT_NAME,
+            payload.as_str(),
+        )?;
+
+        let mut waiter = {
+            let correlation_id = correlation_id.clone();
+            self.wait_for_event_from(
+                TInModel::NAME_SPACE,
+                TInModel::RESPONSE_EVENT_NAME,
+                move |response: &SocketIoRawPayload| {
+                    has_correlation_id(
+                        response.0.as_str(),
+                        TInModel::CORRELATION_ID_FIELD,
+                        correlation_id.as_str(),
+                    )
+                },
+                timeout,
+            )
+            .await
+        };
+
+        let message = SocketIoMessage::Event {
+            namespace: TInModel::NAME_SPACE.to_string().into(),
+            event_name: TInModel::EVENT_NAME.to_string().into(),
+            data: payload.into(),
+            ack: None,
+        };
+
+        // Response has to come through the connection the request was sent to
+        waiter.set_connection(connection.clone());
+
+        connection.send_message(&message.into()).await;
+
+        match waiter.get_result().await {
+            Ok(response) => TOutModel::try_deserialize(response.0.as_str()),
+            Err(SocketIoWaitEventError::Timeout) => Err("Timeout".to_string()),
+            Err(SocketIoWaitEventError::Cancelled) => Err("Cancelled".to_string()),
+            Err(SocketIoWaitEventError::Disconnected) => Err("Socket is disconnected".to_string()),
+        }
+    }
+
+    /// Registers subscriber for the family of events like `price:*`.
+    /// Exact subscribers of the same namespace have priority over the pattern ones
+    pub async fn register_pattern_subscriber<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    >(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: SocketIoEventNamePattern,
+        callbacks: Arc<
+            dyn SocketIoEventPatternSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        let namespace: StrOrString<'static> = namespace.into();
+
+        let subscriber = SocketIoEventPatternSubscriber { callbacks };
+        let subscriber = SocketIoExecutor::create(
+            self.inner.get_default_execution_mode(),
+            Arc::new(subscriber),
+            self.inner.get_panic_reporter(),
+        );
+        let id = self
+            .inner
+            .register_subscriber(namespace.as_str().to_string(), event_name, subscriber)
+            .await;
+
+        SocketIoSubscription::new(id, &self.inner)
+    }
+
+    /// Registers subscriber receiving the arguments of the event as json values.
+    /// Event name can be exact or a pattern
+    pub async fn register_raw_subscriber(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<SocketIoEventNamePattern>,
+        callbacks: Arc<dyn SocketIoRawSubscriberCallback + Send + Sync + 'static>,
+    ) -> SocketIoSubscription {
+        let namespace: StrOrString<'static> = namespace.into();
+
+        let subscriber = SocketIoRawSubscriber { callbacks };
+        let subscriber = SocketIoExecutor::create(
+            self.inner.get_default_execution_mode(),
+            Arc::new(subscriber),
+            self.inner.get_panic_reporter(),
+        );
+        let id = self
+            .inner
+            .register_subscriber(
+                namespace.as_str().to_string(),
+                event_name.into(),
+                subscriber,
+            )
+            .await;
+
+        SocketIoSubscription::new(id, &self.inner)
+    }
+
+    /// Emits json value as the single argument of the event
+    pub async fn emit_raw(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        payload: &serde_json::Value,
+    ) -> Result<(), String> {
+        let Some(connection) = self.inner.get_handshaked_connection().await else {
+            return Err("Socket is not connected".to_string());
+        };
+
+        connection.emit_raw(namespace, event_name, payload).await
+    }
+
+    /// Emits json value as the single argument of the event and returns the arguments of the ack
+    pub async fn emit_raw_with_ack(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        payload: &serde_json::Value,
+    ) -> Result<Vec<serde_json::Value>, String> {
+        let Some(connection) = self.inner.get_handshaked_connection().await else {
+            return Err("Socket is not connected".to_string());
+        };
+
+        connection
+            .emit_raw_with_ack(namespace, event_name, payload)
+            .await
+    }
+
+    /// Registers subscriber for every namespace matching template like `/accounts/{accountId}`.
+    /// Namespaces themselves have to be joined with [`MySocketIoClient::join_namespace`]
+    pub async fn register_namespace_template_subscriber<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    >(
+        &self,
+        namespace_template: &str,
+        event_name: impl Into<StrOrString<'static>>,
+        callbacks: Arc<
+            dyn SocketIoNamespaceTemplateSubscriberCallback<TModel, TOutModel>
+                + Send
+                + Sync
+                + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let subscriber = SocketIoNamespaceTemplateSubscriber { callbacks };
+        let subscriber = SocketIoExecutor::create(
+            self.inner.get_default_execution_mode(),
+            Arc::new(subscriber),
+            self.inner.get_panic_reporter(),
+        );
+        let id = self
+            .inner
             .event_subscribers
-            .register(TModel::NAME_SPACE, TModel::EVENT_NAME, subscriber)
+            .register_namespace_template(
+                SocketIoNamespaceTemplate::new(namespace_template),
+                SocketIoEventNamePattern::exact(event_name.as_str()),
+                subscriber,
+            )
+            .await;
+
+        SocketIoSubscription::new(id, &self.inner)
+    }
+
+    /// Registers `onAny` style subscriber which receives every event of every namespace.
+    /// Its returned payload may answer the ack, so it is always executed inline regardless of
+    /// the default execution mode
+    pub async fn register_any_subscriber(
+        &self,
+        subscriber: Arc<dyn SocketIoAnyEventSubscriber + Send + Sync + 'static>,
+    ) -> SocketIoSubscription {
+        let id = self.inner.register_any_subscriber(None, subscriber).await;
+        SocketIoSubscription::new(id, &self.inner)
+    }
+
+    /// Registers `onAny` style subscriber which receives every event of the namespace.
+    /// Can be used as a default handler of the namespace. Always executed inline
+    pub async fn register_namespace_any_subscriber(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        subscriber: Arc<dyn SocketIoAnyEventSubscriber + Send + Sync + 'static>,
+    ) -> SocketIoSubscription {
+        let namespace: StrOrString<'static> = namespace.into();
+        let id = self
+            .inner
+            .register_any_subscriber(Some(namespace.as_str().to_string()), subscriber)
+            .await;
+
+        SocketIoSubscription::new(id, &self.inner)
+    }
+
+    /// Registers single handler for all the events of the namespace. Events are mapped to the
+    /// variants of [`SocketIoNamespaceEventModel`]. Dedicated subscribers of the namespace have
+    /// priority to answer acks. Always executed inline, use dedicated subscribers with
+    /// [`SocketIoExecutionMode`] for slow handlers
+    pub async fn register_namespace_subscriber<
+        TModel: SocketIoNamespaceEventModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    >(
+        &self,
+        callbacks: Arc<
+            dyn SocketIoNamespaceSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        self.register_namespace_subscriber_to(TModel::NAME_SPACE, callbacks)
+            .await
+    }
+
+    pub async fn register_namespace_subscriber_to<
+        TModel: SocketIoNamespacePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    >(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        callbacks: Arc<
+            dyn SocketIoNamespaceSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+    ) -> SocketIoSubscription {
+        let subscriber = SocketIoNamespaceSubscriber { callbacks };
+        self.register_namespace_any_subscriber(namespace, Arc::new(subscriber))
+            .await
+    }
+
+    /// Registers upcasters bringing older payloads of the event to the current model
+    /// before any subscriber is invoked
+    pub async fn register_upcasters<TModel: SocketIoSubscribeEventModel>(
+        &self,
+        versioning: SocketIoEventVersioning,
+    ) {
+        self.register_upcasters_to(TModel::NAME_SPACE, TModel::EVENT_NAME, versioning)
+            .await
+    }
+
+    pub async fn register_upcasters_to(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        versioning: SocketIoEventVersioning,
+    ) {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        self.inner
+            .register_upcasters(
+                namespace.as_str().to_string(),
+                event_name.as_str().to_string(),
+                versioning,
+            )
+            .await;
+    }
+
+    pub async fn join_namespace(&self, namespace: impl Into<StrOrString<'static>>) {
+        let namespace: StrOrString<'static> = namespace.into();
+        self.inner
+            .join_namespace(namespace.as_str().to_string())
             .await;
     }
 
@@ -68,6 +868,13 @@ impl MySocketIoClient {
     }
 }
 
+/// The socket loop keeps the inner part alive, so the client stops the inbound queue workers itself
+impl Drop for MySocketIoClient {
+    fn drop(&mut self) {
+        self.inner.close_inbound_queues();
+    }
+}
+
 pub(crate) struct WebSocketIoSettings {
     pub socket_io_settings: Arc<dyn SocketIoClientSettings + Send + Sync + 'static>,
 }
@@ -99,3 +906,159 @@ impl WsClientSettings for WebSocketIoSettings {
         Some(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::socket_io_test_utils::*;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct OrderModel {
+        id: i32,
+    }
+
+    #[derive(Serialize)]
+    struct OrderAck {
+        accepted: bool,
+    }
+
+    #[tokio::test]
+    async fn test_on_deserializes_payload_and_answers_ack() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let client = create_client(Arc::new(TestLogger::default()));
+        let (connection, transport) = create_connection();
+
+        let handler_received = received.clone();
+        let _subscription = client
+            .on("/orders", "order", move |order: OrderModel| {
+                let received = handler_received.clone();
+                async move {
+                    let accepted = order.id > 0;
+                    received.lock().unwrap().push(order);
+                    OrderAck { accepted }
+                }
+            })
+            .await;
+
+        client
+            .inner
+            .dispatch_event(create_inbound_event(
+                &connection,
+                "/orders",
+                "order",
+                r#"{"id":5}"#,
+                Some(1),
+            ))
+            .await;
+
+        client
+            .inner
+            .dispatch_event(create_inbound_event(
+                &connection,
+                "/orders",
+                "order",
+                r#"{"id":-1}"#,
+                None,
+            ))
+            .await;
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![OrderModel { id: 5 }, OrderModel { id: -1 }]
+        );
+        assert_eq!(
+            transport.get_acks(),
+            vec![(1, r#"{"accepted":true}"#.to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_on_rejects_malformed_payload_with_serde_error() {
+        let logger = Arc::new(TestLogger::default());
+        let client = create_client(logger.clone());
+        let (connection, transport) = create_connection();
+
+        let handler_called = Arc::new(Mutex::new(false));
+
+        let handler_flag = handler_called.clone();
+        let _subscription = client
+            .on("/orders", "order", move |_: OrderModel| {
+                *handler_flag.lock().unwrap() = true;
+                async move {}
+            })
+            .await;
+
+        let payload = r#"{"id":"x"}"#;
+
+        client
+            .inner
+            .dispatch_event(create_inbound_event(
+                &connection,
+                "/orders",
+                "order",
+                payload,
+                Some(2),
+            ))
+            .await;
+
+        let err = serde_json::from_str::<OrderModel>(payload)
+            .unwrap_err()
+            .to_string();
+
+        assert_eq!(transport.get_acks(), vec![(2, error_payload(err.as_str()))]);
+        assert!(!*handler_called.lock().unwrap());
+        assert_eq!(
+            logger.get_errors(),
+            vec![format!("Can not deserialize payload. Err: {}", err)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_second_start_does_not_spawn_one_more_worker() {
+        let client = create_client(Arc::new(TestLogger::default())).set_inbound_queue(
+            SocketIoInboundQueueSettings {
+                capacity: 4,
+                overflow_policy: SocketIoOverflowPolicy::Block,
+            },
+        );
+
+        let queue = client.inner.get_inbound_queue("/").unwrap();
+
+        assert!(client.inner.start());
+        assert!(!client.inner.start());
+
+        // The client, the worker and the test
+        assert_eq!(Arc::strong_count(&queue), 3);
+    }
+
+    #[tokio::test]
+    async fn test_worker_stops_when_client_is_dropped_while_socket_loop_is_alive() {
+        let client = create_client(Arc::new(TestLogger::default())).set_inbound_queue(
+            SocketIoInboundQueueSettings {
+                capacity: 4,
+                overflow_policy: SocketIoOverflowPolicy::Block,
+            },
+        );
+
+        let queue = client.inner.get_inbound_queue("/").unwrap();
+        client.inner.start();
+
+        // Holds the inner part the same way the socket loop does
+        let socket_loop_inner = client.inner.clone();
+        drop(client);
+
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while Arc::strong_count(&queue) > 2 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(Arc::strong_count(&socket_loop_inner), 1);
+    }
+}
@@ -1,8 +1,11 @@
-use rust_extensions::{Logger, StrOrString};
+use rust_extensions::{date_time::DateTimeAsMicroseconds, Logger, StrOrString};
 use socket_io_utils::{SocketIoContract, SocketIoHandshakeOpenModel, SocketIoMessage};
 use std::{
     collections::HashMap,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
 };
 use tokio::sync::Mutex;
 
@@ -11,7 +14,15 @@ use my_web_socket_client::{
     WsCallback, WsConnection,
 };
 
-use crate::{EventSubscribers, SocketIoCallbacks, SocketIoConnection, WebSocketIoSettings};
+use crate::{
+    catch_unwind, notify_subscriber, EventSubscribers, SocketIoAckSender,
+    SocketIoAnyEventSubscriber, SocketIoCallbacks, SocketIoConnection, SocketIoEventContext,
+    SocketIoEventNamePattern, SocketIoEventObserver, SocketIoEventSubscriberNotification,
+    SocketIoEventVersioning, SocketIoExecutionMode, SocketIoInboundEvent, SocketIoInboundQueue,
+    SocketIoInboundQueueMetrics, SocketIoInboundQueueSettings, SocketIoInboundQueues,
+    SocketIoIncomingEvent, SocketIoNamespaceParams, SocketIoPanicReporter, SocketIoUpcasters,
+    WebSocketIoSettings,
+};
 
 #[derive(Default)]
 pub struct SocketIoContext {
@@ -24,7 +35,16 @@ pub struct ClientInner {
     callbacks: Arc<dyn SocketIoCallbacks + Send + Sync + 'static>,
     context: Mutex<SocketIoContext>,
     pub debug_payloads: AtomicBool,
+    pub disconnect_unused_namespaces: AtomicBool,
+    correlation_id: AtomicU64,
+    pub default_execution_mode: std::sync::Mutex<SocketIoExecutionMode>,
+    #[cfg(feature = "json-schema")]
+    pub schema_validator: std::sync::Mutex<Option<Arc<crate::SocketIoSchemaValidator>>>,
     pub event_subscribers: EventSubscribers,
+    upcasters: Mutex<SocketIoUpcasters>,
+    inbound_queues: std::sync::Mutex<SocketIoInboundQueues>,
+    started: AtomicBool,
+    panic_reporter: Arc<SocketIoPanicReporter>,
     settings: Arc<WebSocketIoSettings>,
     logger: Arc<dyn Logger + Send + Sync + 'static>,
 }
@@ -41,7 +61,16 @@ impl ClientInner {
             callbacks,
             context: Mutex::new(SocketIoContext::default()),
             debug_payloads: AtomicBool::new(false),
+            disconnect_unused_namespaces: AtomicBool::new(false),
+            correlation_id: AtomicU64::new(0),
+            default_execution_mode: std::sync::Mutex::new(SocketIoExecutionMode::Inline),
+            #[cfg(feature = "json-schema")]
+            schema_validator: std::sync::Mutex::new(None),
             event_subscribers: EventSubscribers::new(),
+            upcasters: Mutex::new(SocketIoUpcasters::default()),
+            inbound_queues: std::sync::Mutex::new(SocketIoInboundQueues::default()),
+            started: AtomicBool::new(false),
+            panic_reporter: Arc::new(SocketIoPanicReporter::new(logger.clone())),
             settings,
             logger,
         }
@@ -52,6 +81,57 @@ impl ClientInner {
             .load(std::sync::atomic::Ordering::Relaxed)
     }
 
+    pub fn get_disconnect_unused_namespaces(&self) -> bool {
+        self.disconnect_unused_namespaces
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn get_next_correlation_id(&self) -> u64 {
+        self.correlation_id
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+            + 1
+    }
+
+    pub fn get_logger(&self) -> Arc<dyn Logger + Send + Sync + 'static> {
+        self.logger.clone()
+    }
+
+    pub fn get_panic_reporter(&self) -> Arc<SocketIoPanicReporter> {
+        self.panic_reporter.clone()
+    }
+
+    pub fn get_default_execution_mode(&self) -> SocketIoExecutionMode {
+        *self.default_execution_mode.lock().unwrap()
+    }
+
+    #[cfg(feature = "json-schema")]
+    pub fn get_schema_validator(&self) -> Option<Arc<crate::SocketIoSchemaValidator>> {
+        self.schema_validator.lock().unwrap().clone()
+    }
+
+    /// Returns error if the payload is rejected by the schema validation
+    #[cfg_attr(not(feature = "json-schema"), allow(unused_variables))]
+    fn check_incoming_payload(
+        &self,
+        namespace: &str,
+        event_name: &str,
+        payload: &str,
+    ) -> Result<(), String> {
+        #[cfg(feature = "json-schema")]
+        if let Some(schema_validator) = self.get_schema_validator() {
+            return schema_validator
+                .check(
+                    crate::SocketIoPayloadDirection::Incoming,
+                    namespace,
+                    event_name,
+                    payload,
+                )
+                .map_err(|violation| violation.to_string());
+        }
+
+        Ok(())
+    }
+
     async fn set_current_connection(&self, connection: Arc<SocketIoConnection>) {
         let mut context = self.context.lock().await;
         context.current_connection = Some(connection);
@@ -62,12 +142,318 @@ impl ClientInner {
         context.current_connection.clone().unwrap()
     }
 
+    pub async fn get_handshaked_connection(&self) -> Option<Arc<SocketIoConnection>> {
+        let context = self.context.lock().await;
+        context.handshake_response.as_ref()?;
+        context.current_connection.clone()
+    }
+
+    pub async fn register_subscriber(
+        &self,
+        namespace: String,
+        event_name: SocketIoEventNamePattern,
+        subscriber: Arc<dyn SocketIoEventSubscriberNotification + Send + Sync + 'static>,
+    ) -> u64 {
+        let (id, new_namespace) = self
+            .event_subscribers
+            .register(namespace.clone(), event_name, subscriber)
+            .await;
+
+        if new_namespace {
+            self.connect_to_namespace(namespace).await;
+        }
+
+        id
+    }
+
+    pub async fn register_any_subscriber(
+        &self,
+        namespace: Option<String>,
+        subscriber: Arc<dyn SocketIoAnyEventSubscriber + Send + Sync + 'static>,
+    ) -> u64 {
+        let (id, new_namespace) = self
+            .event_subscribers
+            .register_any(namespace.clone(), subscriber)
+            .await;
+
+        if let (true, Some(namespace)) = (new_namespace, namespace) {
+            self.connect_to_namespace(namespace).await;
+        }
+
+        id
+    }
+
+    pub async fn register_observer(
+        &self,
+        namespace: String,
+        event_name: String,
+        observer: Arc<dyn SocketIoEventObserver + Send + Sync + 'static>,
+    ) -> u64 {
+        let (id, new_namespace) = self
+            .event_subscribers
+            .register_observer(namespace.clone(), event_name, observer)
+            .await;
+
+        if new_namespace {
+            self.connect_to_namespace(namespace).await;
+        }
+
+        id
+    }
+
+    pub async fn unregister_subscriber(&self, id: u64) {
+        let Some(namespace) = self.event_subscribers.unregister(id).await else {
+            return;
+        };
+
+        if !self.get_disconnect_unused_namespaces() {
+            self.event_subscribers.join_namespace(namespace).await;
+            return;
+        }
+
+        if let Some(connection) = self.get_handshaked_connection().await {
+            connection.disconnect_from_namespace(namespace).await;
+        }
+    }
+
+    /// namespace = None sets the queue for all the namespaces without their own queue
+    pub fn set_inbound_queue(
+        &self,
+        namespace: Option<String>,
+        settings: SocketIoInboundQueueSettings,
+    ) {
+        let queue = Arc::new(SocketIoInboundQueue::new(settings));
+        let mut inbound_queues = self.inbound_queues.lock().unwrap();
+
+        match namespace {
+            Some(namespace) => {
+                inbound_queues.by_namespace.insert(namespace, queue);
+            }
+            None => inbound_queues.client = Some(queue),
+        }
+    }
+
+    pub fn get_inbound_queue_metrics(
+        &self,
+        namespace: Option<&str>,
+    ) -> Option<Arc<SocketIoInboundQueueMetrics>> {
+        let inbound_queues = self.inbound_queues.lock().unwrap();
+
+        let queue = match namespace {
+            Some(namespace) => inbound_queues.by_namespace.get(namespace),
+            None => inbound_queues.client.as_ref(),
+        };
+
+        queue.map(|queue| queue.get_metrics())
+    }
+
+    pub fn get_inbound_queue(&self, namespace: &str) -> Option<Arc<SocketIoInboundQueue>> {
+        self.inbound_queues.lock().unwrap().get(namespace)
+    }
+
+    /// Every queue is handled by its own task. Tasks stop when the client is dropped and the queues are closed.
+    /// Returns false if the client is already started, so the second start does not spawn
+    /// one more worker per queue and one more socket loop
+    pub fn start(self: &Arc<Self>) -> bool {
+        if self.started.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+
+        let queues = self.inbound_queues.lock().unwrap().get_all();
+
+        for queue in queues {
+            let inner = Arc::downgrade(self);
+
+            tokio::spawn(async move {
+                while let Some(event) = queue.pop().await {
+                    let Some(inner) = inner.upgrade() else {
+                        break;
+                    };
+
+                    inner.dispatch_event(event).await;
+                }
+            });
+        }
+
+        true
+    }
+
+    /// Stops the inbound queue workers
+    pub fn close_inbound_queues(&self) {
+        self.inbound_queues.lock().unwrap().close_all();
+    }
+
+    pub async fn register_upcasters(
+        &self,
+        namespace: String,
+        event_name: String,
+        versioning: SocketIoEventVersioning,
+    ) {
+        let mut upcasters = self.upcasters.lock().await;
+        upcasters.insert(namespace, event_name, versioning);
+    }
+
+    pub async fn join_namespace(&self, namespace: String) {
+        let new_namespace = self
+            .event_subscribers
+            .join_namespace(namespace.clone())
+            .await;
+
+        if new_namespace {
+            self.connect_to_namespace(namespace).await;
+        }
+    }
+
+    // Namespaces registered after the handshake have to be joined right away
+    async fn connect_to_namespace(&self, namespace: String) {
+        if let Some(connection) = self.get_handshaked_connection().await {
+            connection.subscribe_to_namespaces(vec![namespace]).await;
+        }
+    }
+
     async fn remote_current_connection(&self) -> Arc<SocketIoConnection> {
         let mut context = self.context.lock().await;
         context.handshake_response = None;
         context.current_connection.take().unwrap()
     }
 
+    pub(crate) async fn dispatch_event(&self, event: SocketIoInboundEvent) {
+        const PROCESS: &'static str = "dispatch_event";
+
+        let SocketIoInboundEvent {
+            namespace,
+            event_name,
+            data,
+            ack,
+            received_at,
+            connection,
+        } = event;
+
+        let upcasted = {
+            let upcasters = self.upcasters.lock().await;
+            upcasters.upcast(namespace.as_str(), event_name.as_str(), data.as_str())
+        };
+
+        let (event_name, data) = match upcasted {
+            Some(Ok(upcasted)) => (upcasted.event_name, upcasted.payload),
+            Some(Err(err)) => {
+                let mut ctx = HashMap::new();
+                ctx.insert("namespace".to_string(), namespace.to_string());
+                ctx.insert("eventName".to_string(), event_name.to_string());
+                ctx.insert("name".to_string(), self.client_name.as_str().to_string());
+
+                self.logger.write_error(
+                    PROCESS.to_string(),
+                    format!("Can not upcast event payload. Err: {}", err),
+                    Some(ctx),
+                );
+
+                if let Some(ack) = ack {
+                    SocketIoAckSender::new(connection, namespace, event_name, ack)
+                        .send_error(err.as_str())
+                        .await;
+                }
+
+                return;
+            }
+            None => (event_name, data),
+        };
+
+        let subscribers = self
+            .event_subscribers
+            .get(namespace.as_str(), event_name.as_str())
+            .await;
+
+        let any_subscribers = self.event_subscribers.get_any(namespace.as_str()).await;
+
+        let observers = self
+            .event_subscribers
+            .get_observers(namespace.as_str(), event_name.as_str())
+            .await;
+
+        if subscribers.is_none() && any_subscribers.is_empty() && observers.is_empty() {
+            return;
+        }
+
+        let (subscribers, namespace_params) = match subscribers {
+            Some((subscribers, namespace_params)) => (subscribers, namespace_params),
+            None => (vec![], SocketIoNamespaceParams::default()),
+        };
+
+        if let Err(err) =
+            self.check_incoming_payload(namespace.as_str(), event_name.as_str(), data.as_str())
+        {
+            if let Some(ack) = ack {
+                SocketIoAckSender::new(connection, namespace, event_name, ack)
+                    .send_error(err.as_str())
+                    .await;
+            }
+
+            return;
+        }
+
+        let event = SocketIoIncomingEvent {
+            ctx: SocketIoEventContext {
+                namespace,
+                event_name,
+                namespace_params,
+                ack_id: ack,
+                received_at,
+                sid: connection.get_sid().await,
+                connection: connection.clone(),
+            },
+            data,
+        };
+
+        let mut ack_sender = ack.map(|ack| {
+            SocketIoAckSender::new(
+                connection.clone(),
+                event.ctx.namespace.clone(),
+                event.ctx.event_name.clone(),
+                ack,
+            )
+        });
+
+        // The first registered subscriber answers the ack.
+        // Panic of the handler drops its ack sender, which answers the ack with "Handler panicked"
+        for subscriber in subscribers {
+            if let Err(panic_message) =
+                notify_subscriber(subscriber.as_ref(), &event, ack_sender.take()).await
+            {
+                self.panic_reporter.report(&event, panic_message).await;
+            }
+        }
+
+        for any_subscriber in any_subscribers {
+            let any_result = match catch_unwind(any_subscriber.on_event(&event)).await {
+                Ok(any_result) => any_result,
+                Err(panic_message) => {
+                    self.panic_reporter.report(&event, panic_message).await;
+                    continue;
+                }
+            };
+
+            let Some(any_result) = any_result else {
+                continue;
+            };
+
+            if let Some(ack_sender) = ack_sender.take() {
+                ack_sender.send_raw(any_result).await;
+            }
+        }
+
+        // Nobody answered the ack
+        if let Some(ack_sender) = ack_sender {
+            ack_sender.dismiss();
+        }
+
+        for observer in observers {
+            if let Err(panic_message) = catch_unwind(observer.on_event(&event)).await {
+                self.panic_reporter.report(&event, panic_message).await;
+            }
+        }
+    }
+
     async fn handle_socket_io_message(&self, message: SocketIoMessage) {
         const PROCESS: &'static str = "handle_socket_io_message";
         match message {
@@ -89,26 +475,18 @@ impl ClientInner {
                 data,
                 ack,
             } => {
-                let connection: Arc<SocketIoConnection> = self.get_current_connection().await;
-
-                if let Some(subscriber) = self
-                    .event_subscribers
-                    .get(namespace.as_str(), event_name.as_str())
-                    .await
-                {
-                    let result = subscriber.on_event(data.as_str()).await;
-
-                    if let Some(ack) = ack {
-                        let ack = SocketIoMessage::Ack {
-                            namespace,
-                            event_name,
-                            data: result.into(),
-                            ack,
-                        }
-                        .into();
-
-                        connection.send_message(&ack).await;
-                    }
+                let event = SocketIoInboundEvent {
+                    namespace: namespace.to_string(),
+                    event_name: event_name.to_string(),
+                    data: data.to_string(),
+                    ack,
+                    received_at: DateTimeAsMicroseconds::now(),
+                    connection: self.get_current_connection().await,
+                };
+
+                match self.get_inbound_queue(event.namespace.as_str()) {
+                    Some(inbound_queue) => inbound_queue.push(event).await,
+                    None => self.dispatch_event(event).await,
                 }
             }
             SocketIoMessage::Ack {
@@ -189,6 +567,7 @@ impl ClientInner {
         }
     }
 }
+
 #[async_trait::async_trait]
 impl WsCallback for ClientInner {
     async fn before_start_ws_connect(
@@ -240,11 +619,14 @@ impl WsCallback for ClientInner {
     }
     async fn on_connected(&self, ws_connection: Arc<WsConnection>) {
         let connection = SocketIoConnection::new(ws_connection, self.get_debug_payloads());
+        #[cfg(feature = "json-schema")]
+        let connection = connection.set_schema_validator(self.get_schema_validator());
         let connection = Arc::new(connection);
         self.set_current_connection(connection.clone()).await;
     }
     async fn on_disconnected(&self, _ws_connection: Arc<WsConnection>) {
         let connection = self.remote_current_connection().await;
+        connection.handle_disconnect().await;
 
         let callbacks = self.callbacks.clone();
         let _ = tokio::spawn(async move {
@@ -297,3 +679,276 @@ impl WsCallback for ClientInner {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{socket_io_ack_sender::error_payload, socket_io_test_utils::*};
+
+    struct TestAnySubscriber {
+        name: &'static str,
+        answer: bool,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SocketIoAnyEventSubscriber for TestAnySubscriber {
+        async fn on_event(&self, event: &SocketIoIncomingEvent) -> Option<String> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:{}", self.name, event.data));
+
+            if self.answer {
+                return Some(format!("\"{}\"", self.name));
+            }
+
+            None
+        }
+    }
+
+    struct TestObserver {
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SocketIoEventObserver for TestObserver {
+        async fn on_event(&self, event: &SocketIoIncomingEvent) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("observer:{}", event.data));
+        }
+    }
+
+    fn any_subscriber(
+        name: &'static str,
+        answer: bool,
+        log: &Arc<Mutex<Vec<String>>>,
+    ) -> Arc<TestAnySubscriber> {
+        Arc::new(TestAnySubscriber {
+            name,
+            answer,
+            log: log.clone(),
+        })
+    }
+
+    async fn register(inner: &ClientInner, name: &'static str, log: &Arc<Mutex<Vec<String>>>) {
+        inner
+            .register_subscriber(
+                "/".to_string(),
+                SocketIoEventNamePattern::exact("order"),
+                TestSubscriber::new(name, log),
+            )
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_event_is_delivered_to_all_subscribers_and_first_one_takes_ack() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let inner = create_client_inner(Arc::new(TestLogger::default()));
+        let (connection, transport) = create_connection();
+
+        register(&inner, "first", &log).await;
+        register(&inner, "second", &log).await;
+
+        inner
+            .dispatch_event(create_inbound_event(
+                &connection,
+                "/",
+                "order",
+                "1",
+                Some(7),
+            ))
+            .await;
+
+        assert_eq!(*log.lock().unwrap(), vec!["first:1", "second:1"]);
+        assert_eq!(transport.get_acks(), vec![(7, "\"first\"".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_any_subscriber_answers_only_untaken_ack() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let inner = create_client_inner(Arc::new(TestLogger::default()));
+        let (connection, transport) = create_connection();
+
+        inner
+            .register_any_subscriber(None, any_subscriber("any", true, &log))
+            .await;
+
+        inner
+            .dispatch_event(create_inbound_event(
+                &connection,
+                "/",
+                "order",
+                "1",
+                Some(1),
+            ))
+            .await;
+
+        register(&inner, "dedicated", &log).await;
+
+        inner
+            .dispatch_event(create_inbound_event(
+                &connection,
+                "/",
+                "order",
+                "2",
+                Some(2),
+            ))
+            .await;
+
+        assert_eq!(*log.lock().unwrap(), vec!["any:1", "dedicated:2", "any:2"]);
+        assert_eq!(
+            transport.get_acks(),
+            vec![(1, "\"any\"".to_string()), (2, "\"dedicated\"".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_first_answering_any_subscriber_takes_ack() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let inner = create_client_inner(Arc::new(TestLogger::default()));
+        let (connection, transport) = create_connection();
+
+        inner
+            .register_any_subscriber(None, any_subscriber("silent", false, &log))
+            .await;
+        inner
+            .register_any_subscriber(Some("/".to_string()), any_subscriber("first", true, &log))
+            .await;
+        inner
+            .register_any_subscriber(None, any_subscriber("second", true, &log))
+            .await;
+
+        inner
+            .dispatch_event(create_inbound_event(
+                &connection,
+                "/",
+                "order",
+                "1",
+                Some(3),
+            ))
+            .await;
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["silent:1", "first:1", "second:1"]
+        );
+        assert_eq!(transport.get_acks(), vec![(3, "\"first\"".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_observer_never_takes_ack() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let inner = create_client_inner(Arc::new(TestLogger::default()));
+        let (connection, transport) = create_connection();
+
+        inner
+            .register_observer(
+                "/".to_string(),
+                "order".to_string(),
+                Arc::new(TestObserver { log: log.clone() }),
+            )
+            .await;
+
+        inner
+            .dispatch_event(create_inbound_event(
+                &connection,
+                "/",
+                "order",
+                "1",
+                Some(1),
+            ))
+            .await;
+
+        register(&inner, "dedicated", &log).await;
+
+        inner
+            .dispatch_event(create_inbound_event(
+                &connection,
+                "/",
+                "order",
+                "2",
+                Some(2),
+            ))
+            .await;
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["observer:1", "dedicated:2", "observer:2"]
+        );
+        assert_eq!(transport.get_acks(), vec![(2, "\"dedicated\"".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_event_without_subscribers_is_not_answered() {
+        let inner = create_client_inner(Arc::new(TestLogger::default()));
+        let (connection, transport) = create_connection();
+
+        inner
+            .dispatch_event(create_inbound_event(
+                &connection,
+                "/",
+                "order",
+                "1",
+                Some(1),
+            ))
+            .await;
+
+        assert!(transport.get_sent().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_panicked_subscriber_does_not_stop_other_subscribers() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let logger = Arc::new(TestLogger::default());
+        let callback = Arc::new(TestDeadLetterCallback::default());
+        let inner = create_client_inner(logger.clone());
+        let (connection, transport) = create_connection();
+
+        inner
+            .get_panic_reporter()
+            .set_dead_letter_callback(callback.clone());
+
+        inner
+            .register_subscriber(
+                "/".to_string(),
+                SocketIoEventNamePattern::exact("order"),
+                TestSubscriber::panicking("first", "1", &log),
+            )
+            .await;
+        register(&inner, "second", &log).await;
+        inner
+            .register_any_subscriber(None, any_subscriber("any", true, &log))
+            .await;
+
+        inner
+            .dispatch_event(create_inbound_event(
+                &connection,
+                "/",
+                "order",
+                "1",
+                Some(7),
+            ))
+            .await;
+
+        // The panicked subscriber took the ack, so nobody else answers it
+        assert_eq!(*log.lock().unwrap(), vec!["second:1", "any:1"]);
+        assert_eq!(
+            transport.wait_for_acks(1).await,
+            vec![(7, error_payload("Handler panicked"))]
+        );
+        assert_eq!(
+            logger.get_errors(),
+            vec!["Handler panicked. Msg: first failed on 1"]
+        );
+
+        let dead_letters = callback.get_dead_letters();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].ack_id, Some(7));
+        assert_eq!(dead_letters[0].panic_message, "first failed on 1");
+    }
+}
@@ -4,63 +4,664 @@ use tokio::sync::Mutex;
 
 use crate::*;
 
+#[derive(Clone)]
+struct RegisteredSubscriber {
+    id: u64,
+    subscriber: Arc<dyn SocketIoEventSubscriberNotification + Send + Sync + 'static>,
+}
+
+struct RegisteredObserver {
+    id: u64,
+    namespace: String,
+    event_name: String,
+    observer: Arc<dyn SocketIoEventObserver + Send + Sync + 'static>,
+}
+
+/// Routes events of a namespace. Exact event names have priority over patterns.
+/// Patterns are checked in the order of registration.
+/// Several subscribers can be registered to the same route - all of them are invoked
+#[derive(Default)]
+struct EventRoutes {
+    exact: HashMap<String, Vec<RegisteredSubscriber>>,
+    patterns: Vec<(SocketIoEventNamePattern, Vec<RegisteredSubscriber>)>,
+}
+
+impl EventRoutes {
+    fn insert(&mut self, event_name: SocketIoEventNamePattern, subscriber: RegisteredSubscriber) {
+        match event_name {
+            SocketIoEventNamePattern::Exact(event_name) => {
+                self.exact.entry(event_name).or_default().push(subscriber);
+            }
+            pattern => {
+                let index = self
+                    .patterns
+                    .iter()
+                    .position(|(registered, _)| registered.is_same(&pattern));
+
+                match index {
+                    Some(index) => self.patterns[index].1.push(subscriber),
+                    None => self.patterns.push((pattern, vec![subscriber])),
+                }
+            }
+        }
+    }
+
+    /// Returns true if subscriber was found and removed
+    fn remove(&mut self, id: u64) -> bool {
+        let mut removed = false;
+
+        self.exact.retain(|_, subscribers| {
+            subscribers.retain(|itm| {
+                let found = itm.id == id;
+                removed |= found;
+                !found
+            });
+
+            !subscribers.is_empty()
+        });
+
+        self.patterns.retain_mut(|(_, subscribers)| {
+            subscribers.retain(|itm| {
+                let found = itm.id == id;
+                removed |= found;
+                !found
+            });
+
+            !subscribers.is_empty()
+        });
+
+        removed
+    }
+
+    fn is_empty(&self) -> bool {
+        self.exact.is_empty() && self.patterns.is_empty()
+    }
+
+    fn get(&self, event_name: &str) -> Option<&Vec<RegisteredSubscriber>> {
+        if let Some(subscribers) = self.exact.get(event_name) {
+            return Some(subscribers);
+        }
+
+        self.patterns
+            .iter()
+            .find(|(pattern, _)| pattern.is_match(event_name))
+            .map(|(_, subscribers)| subscribers)
+    }
+}
+
+#[derive(Default)]
+struct EventSubscribersInner {
+    by_namespace: HashMap<String, EventRoutes>,
+    by_namespace_template: Vec<(SocketIoNamespaceTemplate, EventRoutes)>,
+    joined_namespaces: Vec<String>,
+    any_subscribers: Vec<(
+        u64,
+        Option<String>,
+        Arc<dyn SocketIoAnyEventSubscriber + Send + Sync + 'static>,
+    )>,
+    observers: Vec<RegisteredObserver>,
+    last_subscriber_id: u64,
+}
+
+impl EventSubscribersInner {
+    fn has_namespace(&self, namespace: &str) -> bool {
+        self.by_namespace.contains_key(namespace)
+            || self.joined_namespaces.iter().any(|itm| itm == namespace)
+            || self
+                .any_subscribers
+                .iter()
+                .any(|(_, itm, _)| itm.as_deref() == Some(namespace))
+            || self.observers.iter().any(|itm| itm.namespace == namespace)
+    }
+
+    fn get_next_subscriber_id(&mut self) -> u64 {
+        self.last_subscriber_id += 1;
+        self.last_subscriber_id
+    }
+}
+
 pub struct EventSubscribers {
-    items: Mutex<
-        HashMap<
-            &'static str,
-            HashMap<
-                &'static str,
-                Arc<dyn SocketIoEventSubscriberNotification + Send + Sync + 'static>,
-            >,
-        >,
-    >,
+    items: Mutex<EventSubscribersInner>,
 }
 
 impl EventSubscribers {
     pub fn new() -> Self {
         EventSubscribers {
-            items: Mutex::new(HashMap::new()),
+            items: Mutex::new(EventSubscribersInner::default()),
         }
     }
 
+    /// Returns subscriber id and true if the namespace was not known before
     pub async fn register(
         &self,
-        namespace: &'static str,
-        event_name: &'static str,
+        namespace: String,
+        event_name: SocketIoEventNamePattern,
         subscriber: Arc<dyn SocketIoEventSubscriberNotification + Send + Sync + 'static>,
-    ) {
+    ) -> (u64, bool) {
         let mut items = self.items.lock().await;
 
-        if !items.contains_key(namespace) {
-            items.insert(namespace, HashMap::new());
+        let new_namespace = !items.has_namespace(namespace.as_str());
+
+        let id = items.get_next_subscriber_id();
+
+        items
+            .by_namespace
+            .entry(namespace)
+            .or_default()
+            .insert(event_name, RegisteredSubscriber { id, subscriber });
+
+        (id, new_namespace)
+    }
+
+    /// Returns subscriber id
+    pub async fn register_namespace_template(
+        &self,
+        namespace_template: SocketIoNamespaceTemplate,
+        event_name: SocketIoEventNamePattern,
+        subscriber: Arc<dyn SocketIoEventSubscriberNotification + Send + Sync + 'static>,
+    ) -> u64 {
+        let mut items = self.items.lock().await;
+
+        let id = items.get_next_subscriber_id();
+
+        let index = items
+            .by_namespace_template
+            .iter()
+            .position(|(template, _)| template.as_str() == namespace_template.as_str());
+
+        let routes = match index {
+            Some(index) => &mut items.by_namespace_template[index].1,
+            None => {
+                items
+                    .by_namespace_template
+                    .push((namespace_template, EventRoutes::default()));
+                &mut items.by_namespace_template.last_mut().unwrap().1
+            }
+        };
+
+        routes.insert(event_name, RegisteredSubscriber { id, subscriber });
+
+        id
+    }
+
+    /// Returns true if the namespace was not known before
+    pub async fn join_namespace(&self, namespace: String) -> bool {
+        let mut items = self.items.lock().await;
+
+        if items.has_namespace(namespace.as_str()) {
+            return false;
+        }
+
+        items.joined_namespaces.push(namespace);
+        true
+    }
+
+    /// namespace = None means subscriber receives events of all the namespaces.
+    /// Returns subscriber id and true if the namespace was not known before
+    pub async fn register_any(
+        &self,
+        namespace: Option<String>,
+        subscriber: Arc<dyn SocketIoAnyEventSubscriber + Send + Sync + 'static>,
+    ) -> (u64, bool) {
+        let mut items = self.items.lock().await;
+
+        let new_namespace = match namespace.as_deref() {
+            Some(namespace) => !items.has_namespace(namespace),
+            None => false,
+        };
+
+        let id = items.get_next_subscriber_id();
+        items.any_subscribers.push((id, namespace, subscriber));
+
+        (id, new_namespace)
+    }
+
+    /// Observers are kept apart from the subscribers, so they never take the ack or the route.
+    /// Returns observer id and true if the namespace was not known before
+    pub(crate) async fn register_observer(
+        &self,
+        namespace: String,
+        event_name: String,
+        observer: Arc<dyn SocketIoEventObserver + Send + Sync + 'static>,
+    ) -> (u64, bool) {
+        let mut items = self.items.lock().await;
+
+        let new_namespace = !items.has_namespace(namespace.as_str());
+
+        let id = items.get_next_subscriber_id();
+        items.observers.push(RegisteredObserver {
+            id,
+            namespace,
+            event_name,
+            observer,
+        });
+
+        (id, new_namespace)
+    }
+
+    /// Returns namespace which has no subscribers anymore after the removal
+    pub async fn unregister(&self, id: u64) -> Option<String> {
+        let mut items = self.items.lock().await;
+
+        let mut affected_namespace = None;
+
+        for (namespace, routes) in items.by_namespace.iter_mut() {
+            if routes.remove(id) {
+                if routes.is_empty() {
+                    affected_namespace = Some(namespace.to_string());
+                }
+                break;
+            }
+        }
+
+        if let Some(namespace) = affected_namespace.as_ref() {
+            items.by_namespace.remove(namespace.as_str());
         }
 
-        let subscribers = items.get_mut(namespace).unwrap();
+        for (_, routes) in items.by_namespace_template.iter_mut() {
+            if routes.remove(id) {
+                break;
+            }
+        }
+
+        items
+            .by_namespace_template
+            .retain(|(_, routes)| !routes.is_empty());
+
+        if let Some(index) = items
+            .any_subscribers
+            .iter()
+            .position(|(subscriber_id, _, _)| *subscriber_id == id)
+        {
+            let (_, namespace, _) = items.any_subscribers.remove(index);
+            affected_namespace = namespace;
+        }
+
+        if let Some(index) = items.observers.iter().position(|itm| itm.id == id) {
+            let observer = items.observers.remove(index);
+            affected_namespace = Some(observer.namespace);
+        }
+
+        let namespace = affected_namespace?;
 
-        if subscribers.contains_key(event_name) {
-            panic!(
-                "Subscriber to namespace {} and event {} already exists",
-                namespace, event_name
-            );
+        if items.has_namespace(namespace.as_str()) {
+            return None;
         }
 
-        subscribers.insert(event_name, subscriber);
+        Some(namespace)
     }
 
+    pub async fn get_any(
+        &self,
+        namespace: &str,
+    ) -> Vec<Arc<dyn SocketIoAnyEventSubscriber + Send + Sync + 'static>> {
+        let items = self.items.lock().await;
+
+        items
+            .any_subscribers
+            .iter()
+            .filter(|(_, subscriber_namespace, _)| match subscriber_namespace {
+                Some(subscriber_namespace) => subscriber_namespace == namespace,
+                None => true,
+            })
+            .map(|(_, _, subscriber)| subscriber.clone())
+            .collect()
+    }
+
+    pub(crate) async fn get_observers(
+        &self,
+        namespace: &str,
+        event_name: &str,
+    ) -> Vec<Arc<dyn SocketIoEventObserver + Send + Sync + 'static>> {
+        let items = self.items.lock().await;
+
+        items
+            .observers
+            .iter()
+            .filter(|itm| itm.namespace == namespace && itm.event_name == event_name)
+            .map(|itm| itm.observer.clone())
+            .collect()
+    }
+
+    /// Subscribers are returned in the order of registration.
+    /// The first one answers the ack
     pub async fn get(
         &self,
         namespace: &str,
         event_name: &str,
-    ) -> Option<Arc<dyn SocketIoEventSubscriberNotification + Send + Sync + 'static>> {
+    ) -> Option<(
+        Vec<Arc<dyn SocketIoEventSubscriberNotification + Send + Sync + 'static>>,
+        SocketIoNamespaceParams,
+    )> {
         let items = self.items.lock().await;
-        let subscribers = items.get(namespace)?;
-        let subscriber = subscribers.get(event_name)?;
 
-        Some(subscriber.clone())
+        if let Some(subscribers) = items
+            .by_namespace
+            .get(namespace)
+            .and_then(|routes| routes.get(event_name))
+        {
+            return Some((
+                subscribers
+                    .iter()
+                    .map(|itm| itm.subscriber.clone())
+                    .collect(),
+                SocketIoNamespaceParams::default(),
+            ));
+        }
+
+        for (template, routes) in items.by_namespace_template.iter() {
+            let Some(subscribers) = routes.get(event_name) else {
+                continue;
+            };
+
+            if let Some(params) = template.try_match(namespace) {
+                return Some((
+                    subscribers
+                        .iter()
+                        .map(|itm| itm.subscriber.clone())
+                        .collect(),
+                    params,
+                ));
+            }
+        }
+
+        None
     }
 
-    pub async fn get_namespaces(&self) -> Vec<&'static str> {
+    pub async fn get_namespaces(&self) -> Vec<String> {
         let items = self.items.lock().await;
-        items.keys().map(|k| *k).collect()
+
+        let mut result: Vec<String> = items.by_namespace.keys().cloned().collect();
+
+        let other_namespaces = items.joined_namespaces.iter().chain(
+            items
+                .any_subscribers
+                .iter()
+                .filter_map(|(_, namespace, _)| namespace.as_ref())
+                .chain(items.observers.iter().map(|itm| &itm.namespace)),
+        );
+
+        for namespace in other_namespaces {
+            if !result.contains(namespace) {
+                result.push(namespace.clone());
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::socket_io_test_utils::*;
+
+    /// Invokes the routed subscribers and returns their log and the namespace params
+    async fn deliver(
+        subscribers: &EventSubscribers,
+        log: &Arc<Mutex<Vec<String>>>,
+        namespace: &str,
+        event_name: &str,
+    ) -> Option<(Vec<String>, SocketIoNamespaceParams)> {
+        let (subscribers, params) = subscribers.get(namespace, event_name).await?;
+
+        let (connection, _) = create_connection();
+        let event = create_event(&connection, namespace, event_name, event_name, None);
+
+        for subscriber in subscribers {
+            subscriber.on_event(&event, None).await;
+        }
+
+        let delivered = std::mem::take(&mut *log.lock().unwrap());
+        Some((delivered, params))
+    }
+
+    #[tokio::test]
+    async fn test_exact_event_name_has_priority_over_pattern() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let subscribers = EventSubscribers::new();
+
+        subscribers
+            .register(
+                "/".to_string(),
+                SocketIoEventNamePattern::wildcard("price:*"),
+                TestSubscriber::new("pattern", &log),
+            )
+            .await;
+
+        subscribers
+            .register(
+                "/".to_string(),
+                SocketIoEventNamePattern::exact("price:EURUSD"),
+                TestSubscriber::new("exact", &log),
+            )
+            .await;
+
+        let (delivered, _) = deliver(&subscribers, &log, "/", "price:EURUSD")
+            .await
+            .unwrap();
+        assert_eq!(delivered, vec!["exact:price:EURUSD"]);
+
+        let (delivered, _) = deliver(&subscribers, &log, "/", "price:GBPUSD")
+            .await
+            .unwrap();
+        assert_eq!(delivered, vec!["pattern:price:GBPUSD"]);
+
+        assert!(deliver(&subscribers, &log, "/", "order").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_patterns_are_checked_in_registration_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let subscribers = EventSubscribers::new();
+
+        subscribers
+            .register(
+                "/".to_string(),
+                SocketIoEventNamePattern::wildcard("price:*"),
+                TestSubscriber::new("first", &log),
+            )
+            .await;
+
+        subscribers
+            .register(
+                "/".to_string(),
+                SocketIoEventNamePattern::wildcard("*:EURUSD"),
+                TestSubscriber::new("second", &log),
+            )
+            .await;
+
+        let (delivered, _) = deliver(&subscribers, &log, "/", "price:EURUSD")
+            .await
+            .unwrap();
+        assert_eq!(delivered, vec!["first:price:EURUSD"]);
+
+        let (delivered, _) = deliver(&subscribers, &log, "/", "order:EURUSD")
+            .await
+            .unwrap();
+        assert_eq!(delivered, vec!["second:order:EURUSD"]);
+    }
+
+    #[tokio::test]
+    async fn test_exact_namespace_has_priority_over_template() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let subscribers = EventSubscribers::new();
+
+        subscribers
+            .register_namespace_template(
+                SocketIoNamespaceTemplate::new("/rooms/{roomId}"),
+                SocketIoEventNamePattern::exact("message"),
+                TestSubscriber::new("template", &log),
+            )
+            .await;
+
+        subscribers
+            .register(
+                "/rooms/lobby".to_string(),
+                SocketIoEventNamePattern::exact("message"),
+                TestSubscriber::new("exact", &log),
+            )
+            .await;
+
+        let (delivered, params) = deliver(&subscribers, &log, "/rooms/lobby", "message")
+            .await
+            .unwrap();
+        assert_eq!(delivered, vec!["exact:message"]);
+        assert!(params.is_empty());
+
+        let (delivered, params) = deliver(&subscribers, &log, "/rooms/42", "message")
+            .await
+            .unwrap();
+        assert_eq!(delivered, vec!["template:message"]);
+        assert_eq!(params.get("roomId"), Some("42"));
+    }
+
+    #[tokio::test]
+    async fn test_all_subscribers_of_route_are_returned_in_registration_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let subscribers = EventSubscribers::new();
+
+        for name in ["first", "second", "third"] {
+            subscribers
+                .register(
+                    "/".to_string(),
+                    SocketIoEventNamePattern::exact("order"),
+                    TestSubscriber::new(name, &log),
+                )
+                .await;
+        }
+
+        let (delivered, _) = deliver(&subscribers, &log, "/", "order").await.unwrap();
+        assert_eq!(
+            delivered,
+            vec!["first:order", "second:order", "third:order"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unregister_keeps_other_subscribers() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let subscribers = EventSubscribers::new();
+
+        let (first_id, new_namespace) = subscribers
+            .register(
+                "/orders".to_string(),
+                SocketIoEventNamePattern::exact("order"),
+                TestSubscriber::new("first", &log),
+            )
+            .await;
+        assert!(new_namespace);
+
+        let (_, new_namespace) = subscribers
+            .register(
+                "/orders".to_string(),
+                SocketIoEventNamePattern::exact("order"),
+                TestSubscriber::new("second", &log),
+            )
+            .await;
+        assert!(!new_namespace);
+
+        subscribers
+            .register(
+                "/prices".to_string(),
+                SocketIoEventNamePattern::exact("price"),
+                TestSubscriber::new("prices", &log),
+            )
+            .await;
+
+        assert_eq!(subscribers.unregister(first_id).await, None);
+        assert_eq!(subscribers.unregister(first_id).await, None);
+
+        let (delivered, _) = deliver(&subscribers, &log, "/orders", "order")
+            .await
+            .unwrap();
+        assert_eq!(delivered, vec!["second:order"]);
+
+        let (delivered, _) = deliver(&subscribers, &log, "/prices", "price")
+            .await
+            .unwrap();
+        assert_eq!(delivered, vec!["prices:price"]);
+    }
+
+    #[tokio::test]
+    async fn test_unregister_of_last_subscriber_removes_namespace() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let subscribers = EventSubscribers::new();
+
+        let (orders_id, _) = subscribers
+            .register(
+                "/orders".to_string(),
+                SocketIoEventNamePattern::wildcard("order:*"),
+                TestSubscriber::new("orders", &log),
+            )
+            .await;
+
+        subscribers
+            .register(
+                "/prices".to_string(),
+                SocketIoEventNamePattern::exact("price"),
+                TestSubscriber::new("prices", &log),
+            )
+            .await;
+
+        assert_eq!(
+            subscribers.unregister(orders_id).await,
+            Some("/orders".to_string())
+        );
+
+        assert!(deliver(&subscribers, &log, "/orders", "order:new")
+            .await
+            .is_none());
+        assert_eq!(subscribers.get_namespaces().await, vec!["/prices"]);
+
+        let (_, new_namespace) = subscribers
+            .register(
+                "/orders".to_string(),
+                SocketIoEventNamePattern::exact("order"),
+                TestSubscriber::new("orders", &log),
+            )
+            .await;
+        assert!(new_namespace);
+    }
+
+    #[tokio::test]
+    async fn test_namespace_is_kept_while_any_subscriber_uses_it() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let subscribers = EventSubscribers::new();
+
+        let (subscriber_id, _) = subscribers
+            .register(
+                "/orders".to_string(),
+                SocketIoEventNamePattern::exact("order"),
+                TestSubscriber::new("orders", &log),
+            )
+            .await;
+
+        let (_, new_namespace) = subscribers
+            .register_any(Some("/orders".to_string()), Arc::new(SilentAnySubscriber))
+            .await;
+        assert!(!new_namespace);
+
+        let (global_id, new_namespace) = subscribers
+            .register_any(None, Arc::new(SilentAnySubscriber))
+            .await;
+        assert!(!new_namespace);
+
+        assert_eq!(subscribers.unregister(subscriber_id).await, None);
+        assert_eq!(subscribers.unregister(global_id).await, None);
+        assert_eq!(subscribers.get_namespaces().await, vec!["/orders"]);
+        assert_eq!(subscribers.get_any("/orders").await.len(), 1);
+        assert!(subscribers.get_any("/prices").await.is_empty());
+    }
+
+    struct SilentAnySubscriber;
+
+    #[async_trait::async_trait]
+    impl SocketIoAnyEventSubscriber for SilentAnySubscriber {
+        async fn on_event(&self, _: &SocketIoIncomingEvent) -> Option<String> {
+            None
+        }
     }
 }
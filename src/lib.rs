@@ -11,6 +11,8 @@ mod socket_io_callbacks;
 pub use socket_io_callbacks::*;
 
 pub extern crate my_web_socket_client;
+pub extern crate serde;
+pub extern crate serde_json;
 pub extern crate socket_io_utils;
 pub use my_web_socket_client::WsClientSettings;
 mod event_subscribers;
@@ -19,3 +21,57 @@ mod socket_io_rpc_models;
 pub use socket_io_rpc_models::*;
 mod socket_io_settings;
 pub use socket_io_settings::*;
+mod socket_io_namespace_template;
+pub use socket_io_namespace_template::*;
+mod socket_io_any_event_subscriber;
+pub use socket_io_any_event_subscriber::*;
+mod socket_io_event_name_pattern;
+pub use socket_io_event_name_pattern::*;
+mod socket_io_subscription;
+pub use socket_io_subscription::*;
+mod socket_io_closure_subscriber;
+pub use socket_io_closure_subscriber::*;
+mod socket_io_event_observer;
+pub(crate) use socket_io_event_observer::*;
+mod socket_io_event_stream;
+pub use socket_io_event_stream::*;
+mod socket_io_event_waiter;
+pub use socket_io_event_waiter::*;
+mod socket_io_correlated_rpc;
+pub use socket_io_correlated_rpc::*;
+mod socket_io_ack_sender;
+pub use socket_io_ack_sender::*;
+mod socket_io_event_context;
+pub use socket_io_event_context::*;
+mod socket_io_args;
+pub use socket_io_args::*;
+mod socket_io_namespace_subscriber;
+pub use socket_io_namespace_subscriber::*;
+
+mod socket_io_execution_mode;
+pub use socket_io_execution_mode::*;
+mod socket_io_panic_guard;
+pub use socket_io_panic_guard::*;
+mod socket_io_inbound_queue;
+pub use socket_io_inbound_queue::*;
+mod socket_io_batch_subscriber;
+pub use socket_io_batch_subscriber::*;
+mod socket_io_conflating_subscriber;
+pub use socket_io_conflating_subscriber::*;
+mod socket_io_keyed_subscriber;
+pub use socket_io_keyed_subscriber::*;
+mod socket_io_raw_subscriber;
+pub use socket_io_raw_subscriber::*;
+mod socket_io_upcasters;
+pub use socket_io_upcasters::*;
+
+#[cfg(test)]
+mod socket_io_test_utils;
+
+#[cfg(feature = "json-schema")]
+mod socket_io_schema_validator;
+#[cfg(feature = "json-schema")]
+pub use socket_io_schema_validator::*;
+
+#[cfg(feature = "macros")]
+pub use my_socket_io_client_macros::*;
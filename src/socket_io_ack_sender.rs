@@ -0,0 +1,226 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use socket_io_utils::SocketIoMessage;
+
+use crate::{SocketIoConnection, SocketIoSubscribeOutModel};
+
+/// Answers the ack requested by the server. Can be moved to another task and completed later.
+/// Dropping it without sending answers the ack with an error payload
+pub struct SocketIoAckSender {
+    connection: Arc<SocketIoConnection>,
+    namespace: String,
+    event_name: String,
+    ack_id: i64,
+    sent: bool,
+    handler_panicked: Arc<AtomicBool>,
+}
+
+impl SocketIoAckSender {
+    pub(crate) fn new(
+        connection: Arc<SocketIoConnection>,
+        namespace: String,
+        event_name: String,
+        ack_id: i64,
+    ) -> Self {
+        Self {
+            connection,
+            namespace,
+            event_name,
+            ack_id,
+            sent: false,
+            handler_panicked: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Is set by the dispatcher when the handler owning the sender panicked,
+    /// so the dropped sender answers with the panic error
+    pub(crate) fn get_panic_marker(&self) -> Arc<AtomicBool> {
+        self.handler_panicked.clone()
+    }
+
+    pub fn get_ack_id(&self) -> i64 {
+        self.ack_id
+    }
+
+    pub async fn send(self, model: &impl SocketIoSubscribeOutModel) {
+        match model.try_serialize() {
+            Ok(payload) => self.send_raw(payload).await,
+            Err(err) => self.send_error(err.as_str()).await,
+        }
+    }
+
+    pub async fn send_error(self, message: &str) {
+        self.send_raw(error_payload(message)).await;
+    }
+
+    /// Drops the sender without answering the ack
+    pub(crate) fn dismiss(mut self) {
+        self.sent = true;
+    }
+
+    pub async fn send_raw(mut self, payload: String) {
+        self.sent = true;
+        send_ack(
+            self.connection.clone(),
+            self.namespace.clone(),
+            self.event_name.clone(),
+            self.ack_id,
+            payload,
+        )
+        .await;
+    }
+}
+
+impl Drop for SocketIoAckSender {
+    fn drop(&mut self) {
+        if self.sent {
+            return;
+        }
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let message = if std::thread::panicking() || self.handler_panicked.load(Ordering::Relaxed) {
+            "Handler panicked"
+        } else {
+            "Ack was dropped by the handler"
+        };
+
+        runtime.spawn(send_ack(
+            self.connection.clone(),
+            std::mem::take(&mut self.namespace),
+            std::mem::take(&mut self.event_name),
+            self.ack_id,
+            error_payload(message),
+        ));
+    }
+}
+
+/// Event which can not be deserialized never reaches the handler. Server gets the reason through the ack
+pub(crate) async fn reject_event(ack: Option<SocketIoAckSender>, err: String) {
+    if let Some(ack) = ack {
+        ack.send_error(err.as_str()).await;
+    }
+}
+
+pub(crate) fn error_payload(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+async fn send_ack(
+    connection: Arc<SocketIoConnection>,
+    namespace: String,
+    event_name: String,
+    ack_id: i64,
+    payload: String,
+) {
+    // Ack belongs to the connection the event came from. It is meaningless after reconnect
+    if connection.is_disconnected() {
+        return;
+    }
+
+    let ack = SocketIoMessage::Ack {
+        namespace: namespace.into(),
+        event_name: event_name.into(),
+        data: payload.into(),
+        ack: ack_id,
+    }
+    .into();
+
+    connection.send_message(&ack).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::socket_io_test_utils::*;
+
+    #[tokio::test]
+    async fn test_sent_ack() {
+        let (connection, transport) = create_connection();
+
+        SocketIoAckSender::new(connection, "/orders".to_string(), "order".to_string(), 3)
+            .send_raw("true".to_string())
+            .await;
+
+        assert_eq!(
+            transport.get_sent(),
+            vec![TestSentMessage::Ack {
+                namespace: "/orders".to_string(),
+                data: "true".to_string(),
+                ack: 3,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dropped_sender_answers_with_error() {
+        let (connection, transport) = create_connection();
+
+        drop(create_ack_sender(&connection, 1));
+
+        assert_eq!(
+            transport.wait_for_acks(1).await,
+            vec![(1, error_payload("Ack was dropped by the handler"))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dropped_sender_of_panicked_handler() {
+        let (connection, transport) = create_connection();
+
+        let ack = create_ack_sender(&connection, 1);
+        ack.get_panic_marker().store(true, Ordering::Relaxed);
+        drop(ack);
+
+        let ack = create_ack_sender(&connection, 2);
+        let _ = tokio::spawn(async move {
+            let _ack = ack;
+            panic!("Handler failed");
+        })
+        .await;
+
+        let mut acks = transport.wait_for_acks(2).await;
+        acks.sort();
+
+        assert_eq!(
+            acks,
+            vec![
+                (1, error_payload("Handler panicked")),
+                (2, error_payload("Handler panicked"))
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dismissed_sender_does_not_answer() {
+        let (connection, transport) = create_connection();
+
+        create_ack_sender(&connection, 1).dismiss();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(transport.get_sent().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ack_is_not_sent_to_disconnected_connection() {
+        let (connection, transport) = create_connection();
+
+        let dropped = create_ack_sender(&connection, 1);
+        let sent = create_ack_sender(&connection, 2);
+
+        connection.handle_disconnect().await;
+
+        drop(dropped);
+        sent.send_raw("true".to_string()).await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(transport.get_sent().is_empty());
+    }
+}
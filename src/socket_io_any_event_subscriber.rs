@@ -0,0 +1,9 @@
+use crate::SocketIoIncomingEvent;
+
+/// `onAny` style subscriber. Sees every incoming event of the namespace (or of the whole client)
+#[async_trait::async_trait]
+pub trait SocketIoAnyEventSubscriber {
+    /// Returned payload answers the ack only if there is no dedicated subscriber for the event.
+    /// If several any-subscribers return payload - the first registered one wins
+    async fn on_event(&self, event: &SocketIoIncomingEvent) -> Option<String>;
+}
@@ -0,0 +1,262 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    SocketIoRpcOutModel, SocketIoRpcPayloadModel, SocketIoSubscribeOutModel,
+    SocketIoSubscribePayloadModel,
+};
+
+/// Payload of the event or ack is the list of arguments following the event name:
+/// `socket.emit("move", x, y, meta)` comes as `x,y,meta`. Wrapping it into the json array
+/// makes single and multiple arguments deserializable the same way
+pub fn deserialize_args<TArgs: DeserializeOwned>(
+    payload: &str,
+) -> Result<TArgs, serde_json::Error> {
+    let payload = payload.trim();
+
+    if payload.is_empty() {
+        return serde_json::from_str("[]");
+    }
+
+    serde_json::from_str(format!("[{}]", payload).as_str())
+}
+
+/// Serializes tuple `(x, y, meta)` into the list of arguments `x,y,meta`.
+/// Single array argument has to be wrapped into the one element tuple `(items,)`
+pub fn serialize_args<TArgs: SocketIoArgsList>(args: &TArgs) -> Result<String, serde_json::Error> {
+    Ok(join_args(args.to_args()?.as_slice()))
+}
+
+pub(crate) fn join_args(args: &[serde_json::Value]) -> String {
+    let items: Vec<String> = args.iter().map(|itm| itm.to_string()).collect();
+    items.join(",")
+}
+
+/// Tuple which maps to the list of arguments of the event or ack
+pub trait SocketIoArgsList: Sized {
+    fn to_args(&self) -> Result<Vec<serde_json::Value>, serde_json::Error>;
+
+    fn from_args(payload: &str) -> Result<Self, serde_json::Error>
+    where
+        Self: DeserializeOwned;
+}
+
+impl SocketIoArgsList for () {
+    fn to_args(&self) -> Result<Vec<serde_json::Value>, serde_json::Error> {
+        Ok(vec![])
+    }
+
+    fn from_args(payload: &str) -> Result<Self, serde_json::Error> {
+        let args: Vec<serde_json::Value> = deserialize_args(payload)?;
+
+        if !args.is_empty() {
+            return Err(serde::de::Error::invalid_length(
+                args.len(),
+                &"no arguments",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! impl_args_list {
+    ($($name:ident),+) => {
+        impl<$($name: Serialize),+> SocketIoArgsList for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn to_args(&self) -> Result<Vec<serde_json::Value>, serde_json::Error> {
+                let ($($name,)+) = self;
+                Ok(vec![$(serde_json::to_value($name)?),+])
+            }
+
+            fn from_args(payload: &str) -> Result<Self, serde_json::Error>
+            where
+                Self: DeserializeOwned,
+            {
+                deserialize_args(payload)
+            }
+        }
+    };
+}
+
+impl_args_list!(A);
+impl_args_list!(A, B);
+impl_args_list!(A, B, C);
+impl_args_list!(A, B, C, D);
+impl_args_list!(A, B, C, D, E);
+impl_args_list!(A, B, C, D, E, F);
+impl_args_list!(A, B, C, D, E, F, G);
+impl_args_list!(A, B, C, D, E, F, G, H);
+impl_args_list!(A, B, C, D, E, F, G, H, I);
+impl_args_list!(A, B, C, D, E, F, G, H, I, J);
+impl_args_list!(A, B, C, D, E, F, G, H, I, J, K);
+impl_args_list!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Multi-argument event or ack. Tuple `(A, B, C)` maps to the arguments of the event.
+/// Malformed arguments are returned by `try_deserialize`, so the subscriber answers the ack with the error
+#[derive(Debug, Clone)]
+pub struct SocketIoArgs<TArgs>(pub TArgs);
+
+impl<TArgs: SocketIoArgsList + DeserializeOwned> SocketIoSubscribePayloadModel
+    for SocketIoArgs<TArgs>
+{
+    fn deserialize(payload: &str) -> Self {
+        match <Self as SocketIoSubscribePayloadModel>::try_deserialize(payload) {
+            Ok(result) => result,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_deserialize(payload: &str) -> Result<Self, String> {
+        TArgs::from_args(payload).map(Self).map_err(|err| {
+            format!(
+                "Can not deserialize event arguments '{}'. Err: {}",
+                payload, err
+            )
+        })
+    }
+}
+
+impl<TArgs: SocketIoArgsList + DeserializeOwned> SocketIoRpcOutModel for SocketIoArgs<TArgs> {
+    fn deserialize(payload: &str) -> Self {
+        match <Self as SocketIoRpcOutModel>::try_deserialize(payload) {
+            Ok(result) => result,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_deserialize(payload: &str) -> Result<Self, String> {
+        TArgs::from_args(payload).map(Self).map_err(|err| {
+            format!(
+                "Can not deserialize ack arguments '{}'. Err: {}",
+                payload, err
+            )
+        })
+    }
+}
+
+impl<TArgs: SocketIoArgsList> SocketIoRpcPayloadModel for SocketIoArgs<TArgs> {
+    fn serialize(&self) -> String {
+        match <Self as SocketIoRpcPayloadModel>::try_serialize(self) {
+            Ok(result) => result,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_serialize(&self) -> Result<String, String> {
+        serialize_args(&self.0)
+            .map_err(|err| format!("Can not serialize event arguments. Err: {}", err))
+    }
+}
+
+impl<TArgs: SocketIoArgsList> SocketIoSubscribeOutModel for SocketIoArgs<TArgs> {
+    fn serialize(&self) -> String {
+        match <Self as SocketIoSubscribeOutModel>::try_serialize(self) {
+            Ok(result) => result,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_serialize(&self) -> Result<String, String> {
+        serialize_args(&self.0)
+            .map_err(|err| format!("Can not serialize ack arguments. Err: {}", err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_multiple_args() {
+        let args: SocketIoArgs<(i32, String, HashMap<String, bool>)> =
+            SocketIoSubscribePayloadModel::try_deserialize(r#"1,"two",{"three":true}"#).unwrap();
+
+        assert_eq!(args.0 .0, 1);
+        assert_eq!(args.0 .1, "two");
+        assert_eq!(args.0 .2.get("three"), Some(&true));
+    }
+
+    #[test]
+    fn test_deserialize_single_array_arg() {
+        let args: SocketIoArgs<(Vec<i32>,)> =
+            SocketIoSubscribePayloadModel::try_deserialize("[1,2,3]").unwrap();
+
+        assert_eq!(args.0 .0, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_deserialize_no_args() {
+        let args: SocketIoArgs<()> = SocketIoSubscribePayloadModel::try_deserialize("").unwrap();
+        assert_eq!(args.0, ());
+
+        let result: Result<SocketIoArgs<()>, String> =
+            SocketIoSubscribePayloadModel::try_deserialize("1");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_malformed_args_are_returned_as_error() {
+        let result: Result<SocketIoArgs<(i32, i32)>, String> =
+            SocketIoSubscribePayloadModel::try_deserialize("1,");
+        assert!(result.is_err());
+
+        let result: Result<SocketIoArgs<(i32, i32)>, String> =
+            SocketIoRpcOutModel::try_deserialize(r#"1,"two""#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_wrong_amount_of_args_is_returned_as_error() {
+        let result: Result<SocketIoArgs<(i32, i32)>, String> =
+            SocketIoSubscribePayloadModel::try_deserialize("1");
+        assert!(result.is_err());
+
+        let result: Result<SocketIoArgs<(i32, i32)>, String> =
+            SocketIoSubscribePayloadModel::try_deserialize("1,2,3");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_serialize_multiple_args() {
+        let args = SocketIoArgs((1, "two", vec![3]));
+
+        assert_eq!(
+            SocketIoRpcPayloadModel::try_serialize(&args).unwrap(),
+            r#"1,"two",[3]"#
+        );
+    }
+
+    #[test]
+    fn test_single_array_arg_is_not_split() {
+        let args = SocketIoArgs((vec![1, 2, 3],));
+
+        assert_eq!(
+            SocketIoSubscribeOutModel::try_serialize(&args).unwrap(),
+            "[1,2,3]"
+        );
+    }
+
+    #[test]
+    fn test_serialize_no_args() {
+        assert_eq!(serialize_args(&()).unwrap(), "");
+    }
+
+    #[test]
+    fn test_serialize_error_is_returned() {
+        let mut map = HashMap::new();
+        map.insert(vec![1], 1);
+
+        let result = SocketIoRpcPayloadModel::try_serialize(&SocketIoArgs((map,)));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_serialized_args_are_deserialized_back() {
+        let payload = serialize_args(&(1, "two".to_string(), (3, 4))).unwrap();
+        let args: (i32, String, (i32, i32)) = deserialize_args(payload.as_str()).unwrap();
+
+        assert_eq!(args, (1, "two".to_string(), (3, 4)));
+    }
+}
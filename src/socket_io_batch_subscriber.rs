@@ -0,0 +1,327 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::mpsc;
+
+use crate::{
+    catch_unwind, SocketIoAckSender, SocketIoEventSubscriberNotification, SocketIoIncomingEvent,
+    SocketIoPanicReporter, SocketIoSubscribeOutModel, SocketIoSubscribePayloadModel,
+    SEQUENTIAL_QUEUE_SIZE,
+};
+
+/// Events without the requested ack are handled in batches.
+/// Events with the requested ack are handled one by one to answer each ack
+#[async_trait::async_trait]
+pub trait SocketIoBatchSubscriberCallback<
+    TInModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+    TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+>
+{
+    async fn on_events(&self, events: Vec<TInModel>);
+    async fn on_event_with_ack(&self, event_payload: TInModel) -> TOutModel;
+}
+
+enum BatchItem<TModel> {
+    Event(TModel, SocketIoIncomingEvent),
+    EventWithAck(TModel, SocketIoIncomingEvent, SocketIoAckSender),
+}
+
+type BatchCallbacks<TModel, TOutModel> =
+    Arc<dyn SocketIoBatchSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static>;
+
+/// Every event of the batch is reported if the handler of the batch panicked
+async fn flush_batch<
+    TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+    TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+>(
+    callbacks: &BatchCallbacks<TModel, TOutModel>,
+    batch: &mut Vec<TModel>,
+    batch_events: &mut Vec<SocketIoIncomingEvent>,
+    panic_reporter: &SocketIoPanicReporter,
+) {
+    if batch.is_empty() {
+        return;
+    }
+
+    let events = std::mem::take(batch_events);
+
+    if let Err(panic_message) = catch_unwind(callbacks.on_events(std::mem::take(batch))).await {
+        for event in events.iter() {
+            panic_reporter.report(event, panic_message.clone()).await;
+        }
+    }
+}
+
+/// Batch is handled when `max_batch_size` events are accumulated
+/// or `max_delay` has passed since the first event of the batch.
+/// The read loop waits when 1024 events are queued to the worker
+pub struct SocketIoBatchSubscriber<TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static> {
+    sender: mpsc::Sender<BatchItem<TModel>>,
+}
+
+impl<TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static>
+    SocketIoBatchSubscriber<TModel>
+{
+    /// Has to be called inside the tokio runtime
+    pub(crate) fn new<TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static>(
+        max_batch_size: usize,
+        max_delay: Duration,
+        callbacks: BatchCallbacks<TModel, TOutModel>,
+        panic_reporter: Arc<SocketIoPanicReporter>,
+    ) -> Self {
+        let max_batch_size = max_batch_size.max(1);
+
+        let (sender, mut receiver) = mpsc::channel::<BatchItem<TModel>>(SEQUENTIAL_QUEUE_SIZE);
+
+        // Ends when the subscriber is dropped on unsubscribe
+        tokio::spawn(async move {
+            let mut batch = Vec::with_capacity(max_batch_size);
+            let mut batch_events = Vec::with_capacity(max_batch_size);
+
+            while let Some(item) = receiver.recv().await {
+                let mut next_item = Some(item);
+                let deadline = tokio::time::Instant::now() + max_delay;
+
+                while let Some(item) = next_item.take() {
+                    match item {
+                        BatchItem::Event(event_model, event) => {
+                            batch.push(event_model);
+                            batch_events.push(event);
+                        }
+                        BatchItem::EventWithAck(event_model, event, ack) => {
+                            // Keeps the order of events
+                            flush_batch(&callbacks, &mut batch, &mut batch_events, &panic_reporter)
+                                .await;
+
+                            match catch_unwind(callbacks.on_event_with_ack(event_model)).await {
+                                Ok(response) => ack.send(&response).await,
+                                Err(panic_message) => {
+                                    ack.send_error("Handler panicked").await;
+                                    panic_reporter.report(&event, panic_message).await;
+                                }
+                            }
+
+                            break;
+                        }
+                    }
+
+                    if batch.len() >= max_batch_size {
+                        break;
+                    }
+
+                    if let Ok(item) = tokio::time::timeout_at(deadline, receiver.recv()).await {
+                        next_item = item;
+                    }
+                }
+
+                flush_batch(&callbacks, &mut batch, &mut batch_events, &panic_reporter).await;
+            }
+        });
+
+        Self { sender }
+    }
+}
+
+#[async_trait::async_trait]
+impl<TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static>
+    SocketIoEventSubscriberNotification for SocketIoBatchSubscriber<TModel>
+{
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        let event_model = match TModel::try_deserialize(event.data.as_str()) {
+            Ok(event_model) => event_model,
+            Err(err) => return crate::reject_event(ack, err).await,
+        };
+
+        let item = match ack {
+            Some(ack) => BatchItem::EventWithAck(event_model, event.clone(), ack),
+            None => BatchItem::Event(event_model, event.clone()),
+        };
+
+        let _ = self.sender.send(item).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use tokio::time::Instant;
+
+    use super::*;
+    use crate::{socket_io_test_utils::*, SocketIoConnection};
+
+    struct Number(usize);
+
+    impl SocketIoSubscribePayloadModel for Number {
+        fn deserialize(payload: &str) -> Self {
+            Self(payload.parse().unwrap())
+        }
+    }
+
+    struct Handled(usize);
+
+    impl SocketIoSubscribeOutModel for Handled {
+        fn serialize(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    /// Records the batches and the events with ack together with the time they were handled
+    struct BatchHandler {
+        started: Instant,
+        handled: Mutex<Vec<(String, Duration)>>,
+    }
+
+    impl BatchHandler {
+        fn get_handled(&self) -> Vec<String> {
+            self.handled
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(itm, _)| itm.clone())
+                .collect()
+        }
+
+        fn push(&self, item: String) {
+            let elapsed = self.started.elapsed();
+            self.handled.lock().unwrap().push((item, elapsed));
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl SocketIoBatchSubscriberCallback<Number, Handled> for BatchHandler {
+        async fn on_events(&self, events: Vec<Number>) {
+            let numbers: Vec<String> = events.iter().map(|itm| itm.0.to_string()).collect();
+            self.push(format!("batch:{}", numbers.join(",")));
+        }
+
+        async fn on_event_with_ack(&self, event_payload: Number) -> Handled {
+            self.push(format!("ack:{}", event_payload.0));
+            Handled(event_payload.0 * 10)
+        }
+    }
+
+    const MAX_DELAY: Duration = Duration::from_millis(100);
+
+    fn create_subscriber(
+        max_batch_size: usize,
+    ) -> (SocketIoBatchSubscriber<Number>, Arc<BatchHandler>) {
+        let handler = Arc::new(BatchHandler {
+            started: Instant::now(),
+            handled: Mutex::new(Vec::new()),
+        });
+
+        let subscriber = SocketIoBatchSubscriber::new(
+            max_batch_size,
+            MAX_DELAY,
+            handler.clone(),
+            Arc::new(SocketIoPanicReporter::new(Arc::new(TestLogger::default()))),
+        );
+
+        (subscriber, handler)
+    }
+
+    async fn send(
+        subscriber: &SocketIoBatchSubscriber<Number>,
+        connection: &Arc<SocketIoConnection>,
+        number: usize,
+        ack: Option<i64>,
+    ) {
+        let data = number.to_string();
+        let event = create_event(connection, "/", "number", data.as_str(), ack);
+        let ack = ack.map(|ack| create_ack_sender(connection, ack));
+        subscriber.on_event(&event, ack).await;
+    }
+
+    async fn wait_for_handled(handler: &BatchHandler, count: usize) -> Vec<(String, Duration)> {
+        for _ in 0..1000 {
+            if handler.handled.lock().unwrap().len() >= count {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        handler.handled.lock().unwrap().clone()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batch_is_flushed_on_size() {
+        let (connection, _) = create_connection();
+        let (subscriber, handler) = create_subscriber(3);
+
+        for number in 1..=7 {
+            send(&subscriber, &connection, number, None).await;
+        }
+
+        let handled = wait_for_handled(&handler, 2).await;
+
+        assert_eq!(handler.get_handled(), vec!["batch:1,2,3", "batch:4,5,6"]);
+        assert!(handled.iter().all(|(_, elapsed)| *elapsed < MAX_DELAY));
+
+        let handled = wait_for_handled(&handler, 3).await;
+        assert_eq!(handled[2].0, "batch:7");
+        assert!(handled[2].1 >= MAX_DELAY);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batch_is_flushed_on_deadline() {
+        let (connection, _) = create_connection();
+        let (subscriber, handler) = create_subscriber(10);
+
+        send(&subscriber, &connection, 1, None).await;
+        tokio::time::sleep(MAX_DELAY / 2).await;
+        send(&subscriber, &connection, 2, None).await;
+
+        let handled = wait_for_handled(&handler, 1).await;
+
+        assert_eq!(handler.get_handled(), vec!["batch:1,2"]);
+        assert!(handled[0].1 >= MAX_DELAY);
+        assert!(handled[0].1 < MAX_DELAY + MAX_DELAY / 2);
+
+        // The next batch has its own deadline
+        send(&subscriber, &connection, 3, None).await;
+        let handled = wait_for_handled(&handler, 2).await;
+
+        assert_eq!(handled[1].0, "batch:3");
+        assert!(handled[1].1 >= handled[0].1 + MAX_DELAY);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_partial_batch_is_flushed_on_close() {
+        let (connection, _) = create_connection();
+        let (subscriber, handler) = create_subscriber(10);
+
+        send(&subscriber, &connection, 1, None).await;
+        send(&subscriber, &connection, 2, None).await;
+
+        drop(subscriber);
+
+        let handled = wait_for_handled(&handler, 1).await;
+
+        assert_eq!(handler.get_handled(), vec!["batch:1,2"]);
+        assert!(handled[0].1 < MAX_DELAY);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_ack_is_answered_after_accumulated_batch() {
+        let (connection, transport) = create_connection();
+        let (subscriber, handler) = create_subscriber(10);
+
+        send(&subscriber, &connection, 1, None).await;
+        send(&subscriber, &connection, 2, None).await;
+        send(&subscriber, &connection, 3, Some(1)).await;
+        send(&subscriber, &connection, 4, None).await;
+        send(&subscriber, &connection, 5, Some(2)).await;
+
+        wait_for_handled(&handler, 4).await;
+
+        assert_eq!(
+            handler.get_handled(),
+            vec!["batch:1,2", "ack:3", "batch:4", "ack:5"]
+        );
+        assert_eq!(
+            transport.get_acks(),
+            vec![(1, "30".to_string()), (2, "50".to_string())]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use std::{collections::HashMap, future::Future, marker::PhantomData, sync::Arc};
+
+use rust_extensions::Logger;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{SocketIoAckSender, SocketIoEventSubscriberNotification, SocketIoIncomingEvent};
+
+/// Async handler accepted by [`crate::MySocketIoClient::on`].
+/// Implemented for every `Fn(TModel) -> impl Future<Output = impl Serialize>`
+pub trait SocketIoEventHandlerFn<TModel>: Send + Sync + 'static {
+    type Output: Serialize + Send + 'static;
+    type Future: Future<Output = Self::Output> + Send + 'static;
+    fn call(&self, event_payload: TModel) -> Self::Future;
+}
+
+impl<TModel, TOutModel, TFuture, TFn> SocketIoEventHandlerFn<TModel> for TFn
+where
+    TFn: Fn(TModel) -> TFuture + Send + Sync + 'static,
+    TFuture: Future<Output = TOutModel> + Send + 'static,
+    TOutModel: Serialize + Send + 'static,
+{
+    type Output = TOutModel;
+    type Future = TFuture;
+
+    fn call(&self, event_payload: TModel) -> Self::Future {
+        (self)(event_payload)
+    }
+}
+
+pub struct SocketIoClosureSubscriber<TModel, THandler: SocketIoEventHandlerFn<TModel>> {
+    handler: THandler,
+    logger: Arc<dyn Logger + Send + Sync + 'static>,
+    model: PhantomData<fn() -> TModel>,
+}
+
+impl<TModel, THandler: SocketIoEventHandlerFn<TModel>> SocketIoClosureSubscriber<TModel, THandler> {
+    pub fn new(handler: THandler, logger: Arc<dyn Logger + Send + Sync + 'static>) -> Self {
+        Self {
+            handler,
+            logger,
+            model: PhantomData,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<TModel, THandler> SocketIoEventSubscriberNotification
+    for SocketIoClosureSubscriber<TModel, THandler>
+where
+    TModel: DeserializeOwned + Send + 'static,
+    THandler: SocketIoEventHandlerFn<TModel>,
+{
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        const PROCESS: &'static str = "SocketIoClosureSubscriber";
+
+        let event_model: TModel = match serde_json::from_str(event.data.as_str()) {
+            Ok(event_model) => event_model,
+            Err(err) => {
+                self.logger.write_error(
+                    PROCESS.to_string(),
+                    format!("Can not deserialize payload. Err: {}", err),
+                    Some(get_log_context(event)),
+                );
+
+                return crate::reject_event(ack, err.to_string()).await;
+            }
+        };
+
+        let response = self.handler.call(event_model).await;
+
+        let Some(ack) = ack else {
+            return;
+        };
+
+        match serde_json::to_string(&response) {
+            Ok(response) => ack.send_raw(response).await,
+            Err(err) => {
+                self.logger.write_error(
+                    PROCESS.to_string(),
+                    format!("Can not serialize ack. Err: {}", err),
+                    Some(get_log_context(event)),
+                );
+
+                ack.send_error("Can not serialize ack").await;
+            }
+        }
+    }
+}
+
+fn get_log_context(event: &SocketIoIncomingEvent) -> HashMap<String, String> {
+    let mut ctx = HashMap::new();
+    ctx.insert("namespace".to_string(), event.ctx.namespace.clone());
+    ctx.insert("eventName".to_string(), event.ctx.event_name.clone());
+    ctx
+}
@@ -0,0 +1,372 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use tokio::sync::Notify;
+
+use crate::{
+    catch_unwind, SocketIoAckSender, SocketIoEventSubscriberCallback,
+    SocketIoEventSubscriberNotification, SocketIoIncomingEvent, SocketIoPanicReporter,
+    SocketIoSubscribeOutModel, SocketIoSubscribePayloadModel,
+};
+
+type ConflationItem<TModel> = (TModel, SocketIoIncomingEvent, Option<SocketIoAckSender>);
+
+#[derive(Default)]
+pub struct SocketIoConflationMetrics {
+    dropped: AtomicU64,
+    pending: AtomicUsize,
+}
+
+impl SocketIoConflationMetrics {
+    /// Amount of events replaced by the newer ones before they were handled
+    pub fn get_dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Amount of keys waiting for the handler
+    pub fn get_pending(&self) -> usize {
+        self.pending.load(Ordering::Relaxed)
+    }
+}
+
+/// Metrics are updated together with the queue under its lock, so they never go out of sync
+struct ConflationQueue<TKey, TModel> {
+    keys: VecDeque<TKey>,
+    values: HashMap<TKey, ConflationItem<TModel>>,
+    metrics: Arc<SocketIoConflationMetrics>,
+}
+
+impl<TKey: Hash + Eq + Clone, TModel> ConflationQueue<TKey, TModel> {
+    /// Returns the replaced event
+    fn push(&mut self, key: TKey, item: ConflationItem<TModel>) -> Option<ConflationItem<TModel>> {
+        let replaced = self.values.insert(key.clone(), item);
+
+        match replaced {
+            Some(_) => {
+                self.metrics.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            None => {
+                self.keys.push_back(key);
+                self.metrics.pending.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        replaced
+    }
+
+    fn pop(&mut self) -> Option<ConflationItem<TModel>> {
+        let key = self.keys.pop_front()?;
+        let item = self.values.remove(&key)?;
+        self.metrics.pending.fetch_sub(1, Ordering::Relaxed);
+        Some(item)
+    }
+}
+
+struct ConflationState<TKey, TModel> {
+    queue: Mutex<ConflationQueue<TKey, TModel>>,
+    notify: Notify,
+    closed: AtomicBool,
+    metrics: Arc<SocketIoConflationMetrics>,
+}
+
+/// While the handler is busy, newer events replace the queued event of the same key.
+/// Keys are handled in the order they were queued
+pub struct SocketIoConflatingSubscriber<
+    TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+    TKey: Hash + Eq + Clone + Send + 'static,
+> {
+    key_extractor: Box<dyn Fn(&TModel) -> TKey + Send + Sync + 'static>,
+    state: Arc<ConflationState<TKey, TModel>>,
+}
+
+impl<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TKey: Hash + Eq + Clone + Send + 'static,
+    > SocketIoConflatingSubscriber<TModel, TKey>
+{
+    /// Has to be called inside the tokio runtime
+    pub(crate) fn new<TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static>(
+        key_extractor: impl Fn(&TModel) -> TKey + Send + Sync + 'static,
+        callbacks: Arc<
+            dyn SocketIoEventSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+        panic_reporter: Arc<SocketIoPanicReporter>,
+    ) -> Self {
+        let metrics = Arc::new(SocketIoConflationMetrics::default());
+
+        let state = Arc::new(ConflationState {
+            queue: Mutex::new(ConflationQueue {
+                keys: VecDeque::new(),
+                values: HashMap::new(),
+                metrics: metrics.clone(),
+            }),
+            notify: Notify::new(),
+            closed: AtomicBool::new(false),
+            metrics,
+        });
+
+        let worker_state = state.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let next = worker_state.queue.lock().unwrap().pop();
+
+                let Some((event_model, event, ack)) = next else {
+                    if worker_state.closed.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    worker_state.notify.notified().await;
+                    continue;
+                };
+
+                match catch_unwind(callbacks.on_event(event_model)).await {
+                    Ok(response) => {
+                        if let Some(ack) = ack {
+                            ack.send(&response).await;
+                        }
+                    }
+                    Err(panic_message) => {
+                        if let Some(ack) = ack {
+                            ack.send_error("Handler panicked").await;
+                        }
+
+                        panic_reporter.report(&event, panic_message).await;
+                    }
+                }
+            }
+        });
+
+        Self {
+            key_extractor: Box::new(key_extractor),
+            state,
+        }
+    }
+
+    pub fn get_metrics(&self) -> Arc<SocketIoConflationMetrics> {
+        self.state.metrics.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TKey: Hash + Eq + Clone + Send + 'static,
+    > SocketIoEventSubscriberNotification for SocketIoConflatingSubscriber<TModel, TKey>
+{
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        let event_model = match TModel::try_deserialize(event.data.as_str()) {
+            Ok(event_model) => event_model,
+            Err(err) => return crate::reject_event(ack, err).await,
+        };
+        let key = (self.key_extractor)(&event_model);
+
+        let replaced = self
+            .state
+            .queue
+            .lock()
+            .unwrap()
+            .push(key, (event_model, event.clone(), ack));
+
+        match replaced {
+            Some((_, _, replaced_ack)) => {
+                if let Some(replaced_ack) = replaced_ack {
+                    replaced_ack
+                        .send_error("Event was replaced by the newer one")
+                        .await;
+                }
+            }
+            None => {
+                self.state.notify.notify_one();
+            }
+        }
+    }
+}
+
+impl<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TKey: Hash + Eq + Clone + Send + 'static,
+    > Drop for SocketIoConflatingSubscriber<TModel, TKey>
+{
+    fn drop(&mut self) {
+        self.state.closed.store(true, Ordering::Relaxed);
+        self.state.notify.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::sync::Semaphore;
+
+    use super::*;
+    use crate::{error_payload, socket_io_test_utils::*, SocketIoConnection};
+
+    /// Payload is "key:seq"
+    struct PriceModel {
+        key: String,
+        seq: usize,
+    }
+
+    impl SocketIoSubscribePayloadModel for PriceModel {
+        fn deserialize(payload: &str) -> Self {
+            let (key, seq) = payload.split_once(':').unwrap();
+
+            Self {
+                key: key.to_string(),
+                seq: seq.parse().unwrap(),
+            }
+        }
+    }
+
+    struct Handled;
+
+    impl SocketIoSubscribeOutModel for Handled {
+        fn serialize(&self) -> String {
+            "\"handled\"".to_string()
+        }
+    }
+
+    /// Every event waits for the permit of the gate
+    struct GatedHandler {
+        gate: Semaphore,
+        handled: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SocketIoEventSubscriberCallback<PriceModel, Handled> for GatedHandler {
+        async fn on_event(&self, event_payload: PriceModel) -> Handled {
+            self.gate.acquire().await.unwrap().forget();
+
+            self.handled
+                .lock()
+                .unwrap()
+                .push(format!("{}:{}", event_payload.key, event_payload.seq));
+
+            Handled
+        }
+    }
+
+    async fn wait_until(condition: impl Fn() -> bool) {
+        for _ in 0..1000 {
+            if condition() {
+                return;
+            }
+
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    }
+
+    fn create_subscriber() -> (
+        SocketIoConflatingSubscriber<PriceModel, String>,
+        Arc<GatedHandler>,
+    ) {
+        let handler = Arc::new(GatedHandler {
+            gate: Semaphore::new(0),
+            handled: Mutex::new(Vec::new()),
+        });
+
+        let subscriber = SocketIoConflatingSubscriber::new(
+            |price: &PriceModel| price.key.clone(),
+            handler.clone(),
+            Arc::new(SocketIoPanicReporter::new(Arc::new(TestLogger::default()))),
+        );
+
+        (subscriber, handler)
+    }
+
+    async fn send(
+        subscriber: &SocketIoConflatingSubscriber<PriceModel, String>,
+        connection: &Arc<SocketIoConnection>,
+        data: &str,
+        ack: Option<i64>,
+    ) {
+        let event = create_event(connection, "/", "price", data, ack);
+        let ack = ack.map(|ack| create_ack_sender(connection, ack));
+        subscriber.on_event(&event, ack).await;
+    }
+
+    /// Worker takes the first event and waits for the gate, so the next events stay queued
+    async fn block_worker(
+        subscriber: &SocketIoConflatingSubscriber<PriceModel, String>,
+        connection: &Arc<SocketIoConnection>,
+    ) {
+        send(subscriber, connection, "EURUSD:1", None).await;
+
+        let metrics = subscriber.get_metrics();
+        wait_until(|| metrics.get_pending() == 0).await;
+        assert_eq!(metrics.get_pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_queued_event_is_replaced_by_newer_one_of_the_key() {
+        let (connection, transport) = create_connection();
+        let (subscriber, handler) = create_subscriber();
+        let metrics = subscriber.get_metrics();
+
+        block_worker(&subscriber, &connection).await;
+
+        send(&subscriber, &connection, "EURUSD:2", Some(2)).await;
+        send(&subscriber, &connection, "EURUSD:3", Some(3)).await;
+        send(&subscriber, &connection, "EURUSD:4", Some(4)).await;
+
+        assert_eq!(metrics.get_dropped(), 2);
+        assert_eq!(metrics.get_pending(), 1);
+
+        handler.gate.add_permits(10);
+        wait_until(|| handler.handled.lock().unwrap().len() == 2).await;
+
+        assert_eq!(
+            *handler.handled.lock().unwrap(),
+            vec!["EURUSD:1", "EURUSD:4"]
+        );
+
+        let mut acks = transport.wait_for_acks(3).await;
+        acks.sort();
+
+        let replaced = error_payload("Event was replaced by the newer one");
+        assert_eq!(
+            acks,
+            vec![
+                (2, replaced.clone()),
+                (3, replaced),
+                (4, "\"handled\"".to_string())
+            ]
+        );
+
+        assert_eq!(metrics.get_dropped(), 2);
+        assert_eq!(metrics.get_pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_handled_in_the_order_they_were_queued() {
+        let (connection, _) = create_connection();
+        let (subscriber, handler) = create_subscriber();
+        let metrics = subscriber.get_metrics();
+
+        block_worker(&subscriber, &connection).await;
+
+        for data in ["GBPUSD:1", "USDJPY:1", "GBPUSD:2", "BTCUSD:1", "USDJPY:2"] {
+            send(&subscriber, &connection, data, None).await;
+        }
+
+        assert_eq!(metrics.get_dropped(), 2);
+        assert_eq!(metrics.get_pending(), 3);
+
+        handler.gate.add_permits(10);
+        wait_until(|| handler.handled.lock().unwrap().len() == 4).await;
+
+        assert_eq!(
+            *handler.handled.lock().unwrap(),
+            vec!["EURUSD:1", "GBPUSD:2", "USDJPY:2", "BTCUSD:1"]
+        );
+        assert_eq!(metrics.get_pending(), 0);
+    }
+}
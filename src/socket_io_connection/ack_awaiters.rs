@@ -20,4 +20,8 @@ impl AckAwaiters {
     pub fn remove_awaiter(&mut self, ack_id: i64) -> Option<TaskCompletion<String, String>> {
         self.awaiters.remove(&ack_id)
     }
+
+    pub fn remove_all(&mut self) -> Vec<TaskCompletion<String, String>> {
+        self.awaiters.drain().map(|(_, awaiter)| awaiter).collect()
+    }
 }
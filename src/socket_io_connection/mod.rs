@@ -4,3 +4,5 @@ mod socket_io_connection_inner;
 pub use socket_io_connection_inner::*;
 mod ack_awaiters;
 pub use ack_awaiters::*;
+mod socket_io_transport;
+pub(crate) use socket_io_transport::*;
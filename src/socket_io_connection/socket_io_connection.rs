@@ -1,25 +1,75 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
-use my_web_socket_client::{hyper_tungstenite::tungstenite::Message, WsConnection};
-use rust_extensions::TaskCompletion;
+use my_web_socket_client::WsConnection;
+use rust_extensions::{StrOrString, TaskCompletion};
 use socket_io_utils::{SocketIoContract, SocketIoMessage};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Notify};
 
 use super::*;
 use crate::*;
 pub struct SocketIoConnection {
-    ws_connection: Arc<WsConnection>,
+    transport: Arc<dyn SocketIoTransport + Send + Sync + 'static>,
     inner: Mutex<SocketIoConnectionInner>,
-    debug_payload: bool,
+    disconnected: AtomicBool,
+    disconnected_notify: Notify,
+    #[cfg(feature = "json-schema")]
+    schema_validator: Option<Arc<SocketIoSchemaValidator>>,
 }
 
 impl SocketIoConnection {
     pub fn new(ws_connection: Arc<WsConnection>, debug_payload: bool) -> Self {
-        SocketIoConnection {
+        Self::from_transport(Arc::new(SocketIoWsTransport::new(
             ws_connection,
-            inner: Mutex::new(SocketIoConnectionInner::new()),
             debug_payload,
+        )))
+    }
+
+    pub(crate) fn from_transport(
+        transport: Arc<dyn SocketIoTransport + Send + Sync + 'static>,
+    ) -> Self {
+        SocketIoConnection {
+            transport,
+            inner: Mutex::new(SocketIoConnectionInner::new()),
+            disconnected: AtomicBool::new(false),
+            disconnected_notify: Notify::new(),
+            #[cfg(feature = "json-schema")]
+            schema_validator: None,
+        }
+    }
+
+    #[cfg(feature = "json-schema")]
+    pub(crate) fn set_schema_validator(
+        mut self,
+        schema_validator: Option<Arc<SocketIoSchemaValidator>>,
+    ) -> Self {
+        self.schema_validator = schema_validator;
+        self
+    }
+
+    /// Returns error if the payload is rejected by the schema validation
+    #[cfg_attr(not(feature = "json-schema"), allow(unused_variables))]
+    pub(crate) fn check_outgoing_payload(
+        &self,
+        namespace: &str,
+        event_name: &str,
+        payload: &str,
+    ) -> Result<(), String> {
+        #[cfg(feature = "json-schema")]
+        if let Some(schema_validator) = self.schema_validator.as_ref() {
+            return schema_validator
+                .check(
+                    SocketIoPayloadDirection::Outgoing,
+                    namespace,
+                    event_name,
+                    payload,
+                )
+                .map_err(|violation| violation.to_string());
         }
+
+        Ok(())
     }
     pub async fn set_sid(&self, sid: String) {
         let mut inner = self.inner.lock().await;
@@ -31,7 +81,7 @@ impl SocketIoConnection {
         inner.sid.clone()
     }
 
-    pub(crate) async fn subscribe_to_namespaces(&self, namespaces: Vec<&'static str>) {
+    pub(crate) async fn subscribe_to_namespaces(&self, namespaces: Vec<String>) {
         for namespace in namespaces {
             let contract: SocketIoContract = SocketIoMessage::Connect {
                 namespace: namespace.into(),
@@ -43,32 +93,17 @@ impl SocketIoConnection {
         }
     }
 
-    pub async fn send_message(&self, contract: &SocketIoContract) {
-        let payload = contract.serialize();
-
-        if payload.binary_frames.len() == 0 {
-            if self.debug_payload {
-                println!("Sending socket_io text payload: '{:?}'", payload.text_frame);
-            }
-
-            self.ws_connection
-                .send_message(Message::Text(payload.text_frame.into()))
-                .await;
-        } else {
-            if self.debug_payload {
-                println!("Sending socket_io text payload: '{:?}'", payload.text_frame);
-            }
-            let mut payloads = vec![Message::Text(payload.text_frame.into())];
+    pub(crate) async fn disconnect_from_namespace(&self, namespace: String) {
+        let contract: SocketIoContract = SocketIoMessage::Disconnect {
+            namespace: namespace.into(),
+        }
+        .into();
 
-            for binary in payload.binary_frames {
-                if self.debug_payload {
-                    println!("Sending socket_io binary payload: Len:{}", binary.len());
-                }
-                payloads.push(Message::Binary(binary.into()));
-            }
+        self.send_message(&contract).await;
+    }
 
-            self.ws_connection.send_messages(payloads.into_iter()).await;
-        }
+    pub async fn send_message(&self, contract: &SocketIoContract) {
+        self.transport.send_message(contract).await;
     }
 
     pub async fn send_event_with_ack<
@@ -78,6 +113,26 @@ impl SocketIoConnection {
         &self,
         data: &TInModel,
     ) -> Result<TOutModel, String> {
+        self.send_event_with_ack_to(TInModel::NAME_SPACE, TInModel::EVENT_NAME, data)
+            .await
+    }
+
+    pub async fn send_event_with_ack_to<
+        TInModel: SocketIoRpcPayloadModel,
+        TOutModel: SocketIoRpcOutModel,
+    >(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        data: &TInModel,
+    ) -> Result<TOutModel, String> {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let data = data.try_serialize()?;
+
+        self.check_outgoing_payload(namespace.as_str(), event_name.as_str(), data.as_str())?;
+
         let (awaiter, message) = {
             let mut inner = self.inner.lock().await;
 
@@ -87,7 +142,7 @@ impl SocketIoConnection {
 
             let awaiter = task_completion.get_awaiter();
 
-            match inner.active_ack_awaiters.get_mut(TInModel::NAME_SPACE) {
+            match inner.active_ack_awaiters.get_mut(namespace.as_str()) {
                 Some(awaiters) => {
                     awaiters.add_awaiter(ack_id, task_completion);
                 }
@@ -96,16 +151,14 @@ impl SocketIoConnection {
                     awaiters.add_awaiter(ack_id, task_completion);
                     inner
                         .active_ack_awaiters
-                        .insert(TInModel::NAME_SPACE.to_string(), awaiters);
+                        .insert(namespace.as_str().to_string(), awaiters);
                 }
             }
 
-            let data = data.serialize();
-
             let message = SocketIoMessage::Event {
-                namespace: TInModel::NAME_SPACE.into(),
+                namespace: namespace.as_str().to_string().into(),
                 data: data.into(),
-                event_name: TInModel::EVENT_NAME.into(),
+                event_name: event_name.as_str().to_string().into(),
                 ack: ack_id.into(),
             };
 
@@ -116,21 +169,93 @@ impl SocketIoConnection {
 
         let result = awaiter.get_result().await?;
 
-        let result = TOutModel::deserialize(&result);
-        Ok(result)
+        TOutModel::try_deserialize(&result)
     }
 
+    /// Event which can not be serialized or is rejected by the schema validation is not sent.
+    /// Use [`SocketIoConnection::try_send_event_and_forget`] to get the reason
     pub async fn send_event_and_forget<TInModel: SocketIoRpcInModel>(&self, model: &TInModel) {
-        let data = model.serialize();
+        let _ = self.try_send_event_and_forget(model).await;
+    }
+
+    pub async fn send_event_and_forget_to<TInModel: SocketIoRpcPayloadModel>(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        model: &TInModel,
+    ) {
+        let _ = self
+            .try_send_event_and_forget_to(namespace, event_name, model)
+            .await;
+    }
+
+    /// Returns error if the event is not sent
+    pub async fn try_send_event_and_forget<TInModel: SocketIoRpcInModel>(
+        &self,
+        model: &TInModel,
+    ) -> Result<(), String> {
+        self.try_send_event_and_forget_to(TInModel::NAME_SPACE, TInModel::EVENT_NAME, model)
+            .await
+    }
+
+    /// Returns error if the event is not sent
+    pub async fn try_send_event_and_forget_to<TInModel: SocketIoRpcPayloadModel>(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        model: &TInModel,
+    ) -> Result<(), String> {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let data = model.try_serialize()?;
+
+        self.check_outgoing_payload(namespace.as_str(), event_name.as_str(), data.as_str())?;
 
         let message = SocketIoMessage::Event {
-            namespace: TInModel::NAME_SPACE.into(),
-            event_name: TInModel::EVENT_NAME.into(),
+            namespace: namespace.as_str().to_string().into(),
+            event_name: event_name.as_str().to_string().into(),
             data: data.into(),
             ack: None,
         };
 
         self.send_message(&message.into()).await;
+
+        Ok(())
+    }
+
+    /// Emits json value as the single argument of the event
+    pub async fn emit_raw(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        payload: &serde_json::Value,
+    ) -> Result<(), String> {
+        self.try_send_event_and_forget_to(
+            namespace,
+            event_name,
+            &SocketIoRawPayload(payload.to_string()),
+        )
+        .await
+    }
+
+    /// Emits json value as the single argument of the event and returns the arguments of the ack
+    pub async fn emit_raw_with_ack(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        payload: &serde_json::Value,
+    ) -> Result<Vec<serde_json::Value>, String> {
+        let result: SocketIoRawPayload = self
+            .send_event_with_ack_to(
+                namespace,
+                event_name,
+                &SocketIoRawPayload(payload.to_string()),
+            )
+            .await?;
+
+        deserialize_args(result.0.as_str())
+            .map_err(|err| format!("Ack payload is not a valid json. Err: {}", err))
     }
 
     pub(crate) async fn handle_ack_event(&self, namespace: &str, ack_id: i64, data: String) {
@@ -147,7 +272,35 @@ impl SocketIoConnection {
         }
     }
 
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected.load(Ordering::Relaxed)
+    }
+
+    pub async fn wait_until_disconnected(&self) {
+        let notified = self.disconnected_notify.notified();
+
+        if self.is_disconnected() {
+            return;
+        }
+
+        notified.await;
+    }
+
+    /// Fails every awaiting ack and wakes up everyone waiting for the disconnect
+    pub(crate) async fn handle_disconnect(&self) {
+        self.disconnected.store(true, Ordering::Relaxed);
+        self.disconnected_notify.notify_waiters();
+
+        let mut inner = self.inner.lock().await;
+
+        for awaiters in inner.active_ack_awaiters.values_mut() {
+            for mut awaiter in awaiters.remove_all() {
+                awaiter.set_error("Socket is disconnected".to_string());
+            }
+        }
+    }
+
     pub async fn disconnect(&self) {
-        self.ws_connection.disconnect().await;
+        self.transport.disconnect().await;
     }
 }
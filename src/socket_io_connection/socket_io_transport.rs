@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use my_web_socket_client::{hyper_tungstenite::tungstenite::Message, WsConnection};
+use socket_io_utils::SocketIoContract;
+
+/// Delivers socket.io contracts to the server
+#[async_trait::async_trait]
+pub(crate) trait SocketIoTransport {
+    async fn send_message(&self, contract: &SocketIoContract);
+    async fn disconnect(&self);
+}
+
+pub(crate) struct SocketIoWsTransport {
+    ws_connection: Arc<WsConnection>,
+    debug_payload: bool,
+}
+
+impl SocketIoWsTransport {
+    pub fn new(ws_connection: Arc<WsConnection>, debug_payload: bool) -> Self {
+        Self {
+            ws_connection,
+            debug_payload,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SocketIoTransport for SocketIoWsTransport {
+    async fn send_message(&self, contract: &SocketIoContract) {
+        let payload = contract.serialize();
+
+        if payload.binary_frames.len() == 0 {
+            if self.debug_payload {
+                println!("Sending socket_io text payload: '{:?}'", payload.text_frame);
+            }
+
+            self.ws_connection
+                .send_message(Message::Text(payload.text_frame.into()))
+                .await;
+        } else {
+            if self.debug_payload {
+                println!("Sending socket_io text payload: '{:?}'", payload.text_frame);
+            }
+            let mut payloads = vec![Message::Text(payload.text_frame.into())];
+
+            for binary in payload.binary_frames {
+                if self.debug_payload {
+                    println!("Sending socket_io binary payload: Len:{}", binary.len());
+                }
+                payloads.push(Message::Binary(binary.into()));
+            }
+
+            self.ws_connection.send_messages(payloads.into_iter()).await;
+        }
+    }
+
+    async fn disconnect(&self) {
+        self.ws_connection.disconnect().await;
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    SocketIoRpcInModel, SocketIoRpcOutModel, SocketIoRpcPayloadModel, SocketIoSubscribePayloadModel,
+};
+
+/// Request which is answered by the server with a separate event instead of the ack.
+/// The request and the response are matched by the correlation id field of the payload
+pub trait SocketIoCorrelatedRpcInModel: SocketIoRpcInModel {
+    const RESPONSE_EVENT_NAME: &'static str;
+    const CORRELATION_ID_FIELD: &'static str;
+}
+
+pub(crate) struct SocketIoRawPayload(pub String);
+
+impl SocketIoSubscribePayloadModel for SocketIoRawPayload {
+    fn deserialize(payload: &str) -> Self {
+        Self(payload.to_string())
+    }
+}
+
+impl SocketIoRpcPayloadModel for SocketIoRawPayload {
+    fn serialize(&self) -> String {
+        self.0.clone()
+    }
+}
+
+impl SocketIoRpcOutModel for SocketIoRawPayload {
+    fn deserialize(payload: &str) -> Self {
+        Self(payload.to_string())
+    }
+}
+
+pub(crate) fn inject_correlation_id(
+    payload: &str,
+    field_name: &str,
+    correlation_id: &str,
+) -> Result<String, String> {
+    let mut json: serde_json::Value = serde_json::from_str(payload)
+        .map_err(|err| format!("Correlated rpc payload is not a valid json. Err: {}", err))?;
+
+    let Some(object) = json.as_object_mut() else {
+        return Err("Correlated rpc payload must be a json object".to_string());
+    };
+
+    object.insert(
+        field_name.to_string(),
+        serde_json::Value::String(correlation_id.to_string()),
+    );
+
+    Ok(json.to_string())
+}
+
+pub(crate) fn has_correlation_id(payload: &str, field_name: &str, correlation_id: &str) -> bool {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(payload) else {
+        return false;
+    };
+
+    match json.get(field_name) {
+        Some(serde_json::Value::String(value)) => value == correlation_id,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(payload: &str) -> serde_json::Value {
+        serde_json::from_str(payload).unwrap()
+    }
+
+    #[test]
+    fn test_correlation_id_is_injected_into_object() {
+        let payload = inject_correlation_id(r#"{"orderId":5}"#, "requestId", "sid-1").unwrap();
+
+        assert_eq!(
+            parse(payload.as_str()),
+            serde_json::json!({ "orderId": 5, "requestId": "sid-1" })
+        );
+        assert!(has_correlation_id(payload.as_str(), "requestId", "sid-1"));
+    }
+
+    #[test]
+    fn test_existing_correlation_id_is_replaced() {
+        let payload =
+            inject_correlation_id(r#"{"requestId":"old","orderId":5}"#, "requestId", "sid-2")
+                .unwrap();
+
+        assert_eq!(
+            parse(payload.as_str()),
+            serde_json::json!({ "orderId": 5, "requestId": "sid-2" })
+        );
+        assert!(!has_correlation_id(payload.as_str(), "requestId", "old"));
+    }
+
+    #[test]
+    fn test_non_object_payload_is_rejected() {
+        for payload in ["[1,2]", "\"text\"", "5", "null"] {
+            assert_eq!(
+                inject_correlation_id(payload, "requestId", "sid-1"),
+                Err("Correlated rpc payload must be a json object".to_string())
+            );
+        }
+
+        let err = inject_correlation_id("{", "requestId", "sid-1").unwrap_err();
+        assert!(err.starts_with("Correlated rpc payload is not a valid json"));
+    }
+
+    #[test]
+    fn test_has_correlation_id() {
+        assert!(has_correlation_id(
+            r#"{"requestId":"sid-1","ok":true}"#,
+            "requestId",
+            "sid-1"
+        ));
+
+        assert!(!has_correlation_id(
+            r#"{"requestId":"sid-2"}"#,
+            "requestId",
+            "sid-1"
+        ));
+        assert!(!has_correlation_id(
+            r#"{"otherId":"sid-1"}"#,
+            "requestId",
+            "sid-1"
+        ));
+        assert!(!has_correlation_id(r#"{"requestId":1}"#, "requestId", "1"));
+        assert!(!has_correlation_id(r#"["sid-1"]"#, "requestId", "sid-1"));
+        assert!(!has_correlation_id("\"sid-1\"", "requestId", "sid-1"));
+        assert!(!has_correlation_id("{", "requestId", "sid-1"));
+    }
+}
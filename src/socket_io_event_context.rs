@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{SocketIoConnection, SocketIoNamespaceParams};
+
+/// Metadata of the incoming event. `connection` is the one the event came from,
+/// so replies are sent to the right socket even across reconnects
+#[derive(Clone)]
+pub struct SocketIoEventContext {
+    pub namespace: String,
+    pub event_name: String,
+    pub namespace_params: SocketIoNamespaceParams,
+    pub ack_id: Option<i64>,
+    pub received_at: DateTimeAsMicroseconds,
+    pub sid: Option<String>,
+    pub connection: Arc<SocketIoConnection>,
+}
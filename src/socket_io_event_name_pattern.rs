@@ -0,0 +1,152 @@
+/// Event name matcher. Exact matches always have priority over wildcard and regex ones
+#[derive(Debug, Clone)]
+pub enum SocketIoEventNamePattern {
+    Exact(String),
+    /// `*` matches any sequence of characters. Example: `price:*`
+    Wildcard(String),
+    /// Regex is matched against the whole event name when it is created by [`SocketIoEventNamePattern::regex`]
+    Regex(regex::Regex),
+}
+
+impl SocketIoEventNamePattern {
+    pub fn exact(event_name: impl Into<String>) -> Self {
+        Self::Exact(event_name.into())
+    }
+
+    pub fn wildcard(pattern: impl Into<String>) -> Self {
+        Self::Wildcard(pattern.into())
+    }
+
+    /// Pattern has to match the whole event name: `price` does not match `price:EURUSD`
+    pub fn regex(pattern: &str) -> Result<Self, regex::Error> {
+        let regex = regex::Regex::new(format!("^(?:{})$", pattern).as_str())?;
+        Ok(Self::Regex(regex))
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Exact(event_name) => event_name.as_str(),
+            Self::Wildcard(pattern) => pattern.as_str(),
+            Self::Regex(regex) => regex.as_str(),
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Self::Exact(_))
+    }
+
+    pub fn is_match(&self, event_name: &str) -> bool {
+        match self {
+            Self::Exact(expected) => expected == event_name,
+            Self::Wildcard(pattern) => wildcard_match(pattern.as_str(), event_name),
+            Self::Regex(regex) => regex.is_match(event_name),
+        }
+    }
+
+    pub(crate) fn is_same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Exact(a), Self::Exact(b)) => a == b,
+            (Self::Wildcard(a), Self::Wildcard(b)) => a == b,
+            (Self::Regex(a), Self::Regex(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl From<&'static str> for SocketIoEventNamePattern {
+    fn from(event_name: &'static str) -> Self {
+        Self::Exact(event_name.to_string())
+    }
+}
+
+impl From<String> for SocketIoEventNamePattern {
+    fn from(event_name: String) -> Self {
+        Self::Exact(event_name)
+    }
+}
+
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+
+    let first = parts.next().unwrap_or_default();
+
+    if !value.starts_with(first) {
+        return false;
+    }
+
+    let mut rest = &value[first.len()..];
+
+    let parts: Vec<&str> = parts.collect();
+
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("price:*", "price:EURUSD"));
+        assert!(wildcard_match("price:*", "price:"));
+        assert!(!wildcard_match("price:*", "prices:EURUSD"));
+
+        assert!(wildcard_match("*:update", "order:update"));
+        assert!(!wildcard_match("*:update", "order:updated"));
+
+        assert!(wildcard_match("a*b*c", "abc"));
+        assert!(wildcard_match("a*b*c", "a-b-b-c"));
+        assert!(!wildcard_match("a*b*c", "a-c-b"));
+
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("a**b", "ab"));
+    }
+
+    #[test]
+    fn test_wildcard_without_asterisk_is_exact() {
+        assert!(wildcard_match("price", "price"));
+        assert!(!wildcard_match("price", "price:EURUSD"));
+        assert!(!wildcard_match("price", "pric"));
+    }
+
+    #[test]
+    fn test_wildcard_parts_do_not_overlap() {
+        assert!(!wildcard_match("ab*ba", "aba"));
+        assert!(wildcard_match("ab*ba", "abba"));
+    }
+
+    #[test]
+    fn test_regex_matches_whole_event_name() {
+        let pattern = SocketIoEventNamePattern::regex("a").unwrap();
+
+        assert!(pattern.is_match("a"));
+        assert!(!pattern.is_match("xay"));
+
+        let pattern = SocketIoEventNamePattern::regex("price|price:[A-Z]+").unwrap();
+
+        assert!(pattern.is_match("price"));
+        assert!(pattern.is_match("price:EURUSD"));
+        assert!(!pattern.is_match("price:eurusd"));
+    }
+
+    #[test]
+    fn test_exact_match() {
+        let pattern: SocketIoEventNamePattern = "price".into();
+
+        assert!(pattern.is_exact());
+        assert!(pattern.is_match("price"));
+        assert!(!pattern.is_match("price:EURUSD"));
+    }
+}
@@ -0,0 +1,8 @@
+use crate::SocketIoIncomingEvent;
+
+/// Passive listener of the event like stream or waiter. Observers are notified after the subscribers,
+/// never answer the ack and do not change which subscriber the event is routed to
+#[async_trait::async_trait]
+pub(crate) trait SocketIoEventObserver {
+    async fn on_event(&self, event: &SocketIoIncomingEvent);
+}
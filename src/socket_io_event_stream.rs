@@ -0,0 +1,223 @@
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use tokio::sync::mpsc::{Receiver, Sender};
+
+use crate::{
+    SocketIoEventObserver, SocketIoIncomingEvent, SocketIoSubscribePayloadModel,
+    SocketIoSubscription,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SocketIoStreamRecvError {
+    /// Buffer was full and the amount of events were dropped. Stream continues with the next event
+    Lagged(u64),
+    Closed,
+}
+
+enum StreamItem<TModel> {
+    Event(TModel),
+    Lagged(u64),
+}
+
+/// Events of the subscription delivered through a bounded buffer.
+/// Dropping the stream unregisters the listener.
+/// [`SocketIoStreamRecvError::Lagged`] is returned after the events buffered before the gap
+pub struct SocketIoEventStream<TModel> {
+    receiver: Receiver<StreamItem<TModel>>,
+    dropped: Arc<AtomicU64>,
+    subscription: Option<SocketIoSubscription>,
+}
+
+impl<TModel> SocketIoEventStream<TModel> {
+    fn new(receiver: Receiver<StreamItem<TModel>>, dropped: Arc<AtomicU64>) -> Self {
+        Self {
+            receiver,
+            dropped,
+            subscription: None,
+        }
+    }
+
+    pub(crate) fn set_subscription(&mut self, subscription: SocketIoSubscription) {
+        self.subscription = Some(subscription);
+    }
+
+    pub async fn recv(&mut self) -> Result<TModel, SocketIoStreamRecvError> {
+        std::future::poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    pub fn poll_recv(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<TModel, SocketIoStreamRecvError>> {
+        let result = match self.receiver.poll_recv(cx) {
+            Poll::Ready(Some(StreamItem::Event(event))) => return Poll::Ready(Ok(event)),
+            Poll::Ready(Some(StreamItem::Lagged(dropped))) => {
+                return Poll::Ready(Err(SocketIoStreamRecvError::Lagged(dropped)))
+            }
+            Poll::Ready(None) => Poll::Ready(Err(SocketIoStreamRecvError::Closed)),
+            Poll::Pending => Poll::Pending,
+        };
+
+        // Buffer is drained, so the gap which is not queued yet goes right after the delivered events
+        let dropped = self.dropped.swap(0, Ordering::Relaxed);
+
+        if dropped > 0 {
+            return Poll::Ready(Err(SocketIoStreamRecvError::Lagged(dropped)));
+        }
+
+        result
+    }
+
+    pub async fn unsubscribe(mut self) {
+        if let Some(subscription) = self.subscription.take() {
+            subscription.unsubscribe().await;
+        }
+    }
+}
+
+/// Ends when the stream is closed. Lagged gaps are yielded as errors
+impl<TModel> futures_core::Stream for SocketIoEventStream<TModel> {
+    type Item = Result<TModel, SocketIoStreamRecvError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.get_mut().poll_recv(cx) {
+            Poll::Ready(Err(SocketIoStreamRecvError::Closed)) => Poll::Ready(None),
+            Poll::Ready(result) => Poll::Ready(Some(result)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+pub(crate) struct SocketIoStreamObserver<TModel: SocketIoSubscribePayloadModel + Send + 'static> {
+    sender: Sender<StreamItem<TModel>>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl<TModel: SocketIoSubscribePayloadModel + Send + 'static> SocketIoStreamObserver<TModel> {
+    pub fn new(buffer_size: usize) -> (Self, SocketIoEventStream<TModel>) {
+        let (sender, receiver) = tokio::sync::mpsc::channel(buffer_size.max(1));
+        let dropped = Arc::new(AtomicU64::new(0));
+
+        let observer = Self {
+            sender,
+            dropped: dropped.clone(),
+        };
+
+        (observer, SocketIoEventStream::new(receiver, dropped))
+    }
+
+    /// Never blocks the socket read loop. Consumer is notified about the dropped events instead.
+    /// The gap is queued before the next event, so the consumer sees it in the right place
+    fn push(&self, event_model: TModel) {
+        let dropped = self.dropped.swap(0, Ordering::Relaxed);
+
+        if dropped > 0 && self.sender.try_send(StreamItem::Lagged(dropped)).is_err() {
+            self.dropped.fetch_add(dropped + 1, Ordering::Relaxed);
+            return;
+        }
+
+        if self
+            .sender
+            .try_send(StreamItem::Event(event_model))
+            .is_err()
+        {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<TModel: SocketIoSubscribePayloadModel + Send + 'static> SocketIoEventObserver
+    for SocketIoStreamObserver<TModel>
+{
+    async fn on_event(&self, event: &SocketIoIncomingEvent) {
+        if let Ok(event_model) = TModel::try_deserialize(event.data.as_str()) {
+            self.push(event_model);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct TestModel(u64);
+
+    impl SocketIoSubscribePayloadModel for TestModel {
+        fn deserialize(payload: &str) -> Self {
+            Self(payload.parse().unwrap())
+        }
+    }
+
+    fn push(observer: &SocketIoStreamObserver<TestModel>, value: u64) {
+        observer.push(TestModel(value));
+    }
+
+    #[tokio::test]
+    async fn test_lagged_is_returned_after_buffered_events() {
+        let (observer, mut stream) = SocketIoStreamObserver::<TestModel>::new(2);
+
+        push(&observer, 1);
+        push(&observer, 2);
+        push(&observer, 3);
+        push(&observer, 4);
+
+        assert_eq!(stream.recv().await, Ok(TestModel(1)));
+        assert_eq!(stream.recv().await, Ok(TestModel(2)));
+
+        push(&observer, 5);
+
+        assert_eq!(stream.recv().await, Err(SocketIoStreamRecvError::Lagged(2)));
+        assert_eq!(stream.recv().await, Ok(TestModel(5)));
+    }
+
+    #[tokio::test]
+    async fn test_lagged_is_returned_when_buffer_is_drained() {
+        let (observer, mut stream) = SocketIoStreamObserver::<TestModel>::new(1);
+
+        push(&observer, 1);
+        push(&observer, 2);
+
+        assert_eq!(stream.recv().await, Ok(TestModel(1)));
+        assert_eq!(stream.recv().await, Err(SocketIoStreamRecvError::Lagged(1)));
+
+        push(&observer, 3);
+
+        assert_eq!(stream.recv().await, Ok(TestModel(3)));
+    }
+
+    #[tokio::test]
+    async fn test_zero_buffer_size_does_not_panic() {
+        let (observer, mut stream) = SocketIoStreamObserver::<TestModel>::new(0);
+
+        push(&observer, 1);
+
+        assert_eq!(stream.recv().await, Ok(TestModel(1)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_when_closed() {
+        use std::future::poll_fn;
+
+        let (observer, mut stream) = SocketIoStreamObserver::<TestModel>::new(4);
+
+        push(&observer, 1);
+        drop(observer);
+
+        let mut stream = Pin::new(&mut stream);
+
+        let next = poll_fn(|cx| futures_core::Stream::poll_next(stream.as_mut(), cx)).await;
+        assert_eq!(next, Some(Ok(TestModel(1))));
+
+        let next = poll_fn(|cx| futures_core::Stream::poll_next(stream.as_mut(), cx)).await;
+        assert_eq!(next, None);
+    }
+}
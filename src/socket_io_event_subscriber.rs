@@ -1,13 +1,53 @@
 use std::sync::Arc;
 
+use crate::{SocketIoAckSender, SocketIoEventContext, SocketIoNamespaceParams};
+
 pub trait SocketIoSubscribeEventModel {
     const NAME_SPACE: &'static str;
     const EVENT_NAME: &'static str;
     fn deserialize(payload: &str) -> Self;
+
+    /// Subscribers use it to answer the ack with the error instead of panicking on the malformed payload
+    fn try_deserialize(payload: &str) -> Result<Self, String>
+    where
+        Self: Sized,
+    {
+        Ok(<Self as SocketIoSubscribeEventModel>::deserialize(payload))
+    }
+}
+
+/// Payload part of the event model. Is used when namespace and event name are known only at runtime
+pub trait SocketIoSubscribePayloadModel {
+    fn deserialize(payload: &str) -> Self;
+
+    /// Subscribers use it to answer the ack with the error instead of panicking on the malformed payload
+    fn try_deserialize(payload: &str) -> Result<Self, String>
+    where
+        Self: Sized,
+    {
+        Ok(<Self as SocketIoSubscribePayloadModel>::deserialize(
+            payload,
+        ))
+    }
+}
+
+impl<T: SocketIoSubscribeEventModel> SocketIoSubscribePayloadModel for T {
+    fn deserialize(payload: &str) -> Self {
+        <T as SocketIoSubscribeEventModel>::deserialize(payload)
+    }
+
+    fn try_deserialize(payload: &str) -> Result<Self, String> {
+        <T as SocketIoSubscribeEventModel>::try_deserialize(payload)
+    }
 }
 
 pub trait SocketIoSubscribeOutModel {
     fn serialize(&self) -> String;
+
+    /// Ack sender uses it to answer the ack with the error instead of panicking
+    fn try_serialize(&self) -> Result<String, String> {
+        Ok(self.serialize())
+    }
 }
 
 impl SocketIoSubscribeOutModel for () {
@@ -18,20 +58,73 @@ impl SocketIoSubscribeOutModel for () {
 
 #[async_trait::async_trait]
 pub trait SocketIoEventSubscriberCallback<
-    TInModel: SocketIoSubscribeEventModel + Send + Sync + 'static,
+    TInModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
     TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
 >
 {
     async fn on_event(&self, event_payload: TInModel) -> TOutModel;
 }
 
+#[async_trait::async_trait]
+pub trait SocketIoNamespaceTemplateSubscriberCallback<
+    TInModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+    TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+>
+{
+    async fn on_event(
+        &self,
+        namespace_params: SocketIoNamespaceParams,
+        event_payload: TInModel,
+    ) -> TOutModel;
+}
+
+#[async_trait::async_trait]
+pub trait SocketIoEventPatternSubscriberCallback<
+    TInModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+    TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+>
+{
+    async fn on_event(&self, event_name: String, event_payload: TInModel) -> TOutModel;
+}
+
+/// Subscriber which answers the ack later. `ack` is None if the server did not request it
+#[async_trait::async_trait]
+pub trait SocketIoDeferredAckSubscriberCallback<
+    TInModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+>
+{
+    async fn on_event(&self, event_payload: TInModel, ack: Option<SocketIoAckSender>);
+}
+
+#[async_trait::async_trait]
+pub trait SocketIoContextSubscriberCallback<
+    TInModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+    TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+>
+{
+    async fn on_event(&self, ctx: &SocketIoEventContext, event_payload: TInModel) -> TOutModel;
+}
+
+#[derive(Clone)]
+pub struct SocketIoIncomingEvent {
+    pub ctx: SocketIoEventContext,
+    pub data: String,
+}
+
+impl SocketIoIncomingEvent {
+    pub fn get_args(&self) -> Result<Vec<serde_json::Value>, serde_json::Error> {
+        crate::deserialize_args(self.data.as_str())
+    }
+}
+
+/// Subscriber receives `ack` only if the server requested it and the subscriber is the one who answers it
 #[async_trait::async_trait]
 pub trait SocketIoEventSubscriberNotification {
-    async fn on_event(&self, event_payload: &str) -> String;
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>);
 }
 
 pub struct SocketIoEventSubscriber<
-    TModel: SocketIoSubscribeEventModel + Send + Sync + 'static,
+    TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
     TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
 > {
     pub callbacks:
@@ -40,14 +133,127 @@ pub struct SocketIoEventSubscriber<
 
 #[async_trait::async_trait]
 impl<
-        TModel: SocketIoSubscribeEventModel + Send + Sync + 'static,
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
         TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
     > SocketIoEventSubscriberNotification for SocketIoEventSubscriber<TModel, TOutModel>
 {
-    async fn on_event(&self, event_payload: &str) -> String {
-        let event_model: TModel = TModel::deserialize(event_payload);
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        let event_model = match TModel::try_deserialize(event.data.as_str()) {
+            Ok(event_model) => event_model,
+            Err(err) => return crate::reject_event(ack, err).await,
+        };
         let response = self.callbacks.on_event(event_model).await;
 
-        response.serialize()
+        if let Some(ack) = ack {
+            ack.send(&response).await;
+        }
+    }
+}
+
+pub struct SocketIoNamespaceTemplateSubscriber<
+    TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+    TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+> {
+    pub callbacks: Arc<
+        dyn SocketIoNamespaceTemplateSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+    >,
+}
+
+#[async_trait::async_trait]
+impl<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    > SocketIoEventSubscriberNotification
+    for SocketIoNamespaceTemplateSubscriber<TModel, TOutModel>
+{
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        let event_model = match TModel::try_deserialize(event.data.as_str()) {
+            Ok(event_model) => event_model,
+            Err(err) => return crate::reject_event(ack, err).await,
+        };
+        let response = self
+            .callbacks
+            .on_event(event.ctx.namespace_params.clone(), event_model)
+            .await;
+
+        if let Some(ack) = ack {
+            ack.send(&response).await;
+        }
+    }
+}
+
+pub struct SocketIoEventPatternSubscriber<
+    TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+    TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+> {
+    pub callbacks:
+        Arc<dyn SocketIoEventPatternSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static>,
+}
+
+#[async_trait::async_trait]
+impl<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    > SocketIoEventSubscriberNotification for SocketIoEventPatternSubscriber<TModel, TOutModel>
+{
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        let event_model = match TModel::try_deserialize(event.data.as_str()) {
+            Ok(event_model) => event_model,
+            Err(err) => return crate::reject_event(ack, err).await,
+        };
+        let response = self
+            .callbacks
+            .on_event(event.ctx.event_name.clone(), event_model)
+            .await;
+
+        if let Some(ack) = ack {
+            ack.send(&response).await;
+        }
+    }
+}
+
+pub struct SocketIoDeferredAckSubscriber<
+    TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+> {
+    pub callbacks: Arc<dyn SocketIoDeferredAckSubscriberCallback<TModel> + Send + Sync + 'static>,
+}
+
+#[async_trait::async_trait]
+impl<TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static>
+    SocketIoEventSubscriberNotification for SocketIoDeferredAckSubscriber<TModel>
+{
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        let event_model = match TModel::try_deserialize(event.data.as_str()) {
+            Ok(event_model) => event_model,
+            Err(err) => return crate::reject_event(ack, err).await,
+        };
+        self.callbacks.on_event(event_model, ack).await;
+    }
+}
+
+pub struct SocketIoContextSubscriber<
+    TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+    TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+> {
+    pub callbacks:
+        Arc<dyn SocketIoContextSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static>,
+}
+
+#[async_trait::async_trait]
+impl<
+        TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    > SocketIoEventSubscriberNotification for SocketIoContextSubscriber<TModel, TOutModel>
+{
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        let event_model = match TModel::try_deserialize(event.data.as_str()) {
+            Ok(event_model) => event_model,
+            Err(err) => return crate::reject_event(ack, err).await,
+        };
+        let response = self.callbacks.on_event(&event.ctx, event_model).await;
+
+        if let Some(ack) = ack {
+            ack.send(&response).await;
+        }
     }
 }
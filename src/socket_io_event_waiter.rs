@@ -0,0 +1,220 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::{oneshot, Mutex};
+
+use crate::{
+    SocketIoConnection, SocketIoEventObserver, SocketIoIncomingEvent,
+    SocketIoSubscribePayloadModel, SocketIoSubscription,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SocketIoWaitEventError {
+    Timeout,
+    Cancelled,
+    Disconnected,
+}
+
+/// Temporary listener created by [`crate::MySocketIoClient::wait_for_event`].
+/// It is registered before the command is emitted, so the answer can not be missed.
+/// Waiter only observes the event: it does not answer the ack and does not take the event from the subscribers.
+/// Waiter fails as soon as the connection it was created on is disconnected
+pub struct SocketIoEventWaiter<TModel> {
+    receiver: oneshot::Receiver<TModel>,
+    timeout: Duration,
+    subscription: Option<SocketIoSubscription>,
+    connection: Option<Arc<SocketIoConnection>>,
+}
+
+impl<TModel> SocketIoEventWaiter<TModel> {
+    pub(crate) fn new(receiver: oneshot::Receiver<TModel>, timeout: Duration) -> Self {
+        Self {
+            receiver,
+            timeout,
+            subscription: None,
+            connection: None,
+        }
+    }
+
+    pub(crate) fn set_connection(&mut self, connection: Arc<SocketIoConnection>) {
+        self.connection = Some(connection);
+    }
+
+    pub(crate) fn set_subscription(&mut self, subscription: SocketIoSubscription) {
+        self.subscription = Some(subscription);
+    }
+
+    pub async fn get_result(mut self) -> Result<TModel, SocketIoWaitEventError> {
+        let result = tokio::select! {
+            result = tokio::time::timeout(self.timeout, &mut self.receiver) => match result {
+                Ok(Ok(event)) => Ok(event),
+                Ok(Err(_)) => Err(SocketIoWaitEventError::Cancelled),
+                Err(_) => Err(SocketIoWaitEventError::Timeout),
+            },
+            _ = wait_until_disconnected(self.connection.as_deref()) => {
+                Err(SocketIoWaitEventError::Disconnected)
+            }
+        };
+
+        if let Some(subscription) = self.subscription.take() {
+            subscription.unsubscribe().await;
+        }
+
+        result
+    }
+}
+
+async fn wait_until_disconnected(connection: Option<&SocketIoConnection>) {
+    match connection {
+        Some(connection) => connection.wait_until_disconnected().await,
+        None => std::future::pending().await,
+    }
+}
+
+pub(crate) struct SocketIoEventWaiterObserver<
+    TModel: SocketIoSubscribePayloadModel + Send + 'static,
+    TPredicate: Fn(&TModel) -> bool + Send + Sync + 'static,
+> {
+    sender: Mutex<Option<oneshot::Sender<TModel>>>,
+    predicate: TPredicate,
+}
+
+impl<
+        TModel: SocketIoSubscribePayloadModel + Send + 'static,
+        TPredicate: Fn(&TModel) -> bool + Send + Sync + 'static,
+    > SocketIoEventWaiterObserver<TModel, TPredicate>
+{
+    pub fn new(predicate: TPredicate, timeout: Duration) -> (Self, SocketIoEventWaiter<TModel>) {
+        let (sender, receiver) = oneshot::channel();
+
+        let observer = Self {
+            sender: Mutex::new(Some(sender)),
+            predicate,
+        };
+
+        (observer, SocketIoEventWaiter::new(receiver, timeout))
+    }
+}
+
+#[async_trait::async_trait]
+impl<
+        TModel: SocketIoSubscribePayloadModel + Send + 'static,
+        TPredicate: Fn(&TModel) -> bool + Send + Sync + 'static,
+    > SocketIoEventObserver for SocketIoEventWaiterObserver<TModel, TPredicate>
+{
+    async fn on_event(&self, event: &SocketIoIncomingEvent) {
+        let mut sender = self.sender.lock().await;
+
+        if sender.is_none() {
+            return;
+        }
+
+        let Ok(event_model) = TModel::try_deserialize(event.data.as_str()) else {
+            return;
+        };
+
+        if (self.predicate)(&event_model) {
+            if let Some(sender) = sender.take() {
+                let _ = sender.send(event_model);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{socket_io_test_utils::*, SocketIoRawPayload};
+
+    fn create_waiter(
+        timeout: Duration,
+    ) -> (
+        impl SocketIoEventObserver,
+        SocketIoEventWaiter<SocketIoRawPayload>,
+    ) {
+        SocketIoEventWaiterObserver::new(
+            |event: &SocketIoRawPayload| event.0.contains("filled"),
+            timeout,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_first_matching_event_resolves_waiter() {
+        let (connection, _) = create_connection();
+        let (observer, waiter) = create_waiter(Duration::from_secs(1));
+
+        for data in ["\"pending\"", "\"filled-1\"", "\"filled-2\""] {
+            observer
+                .on_event(&create_event(&connection, "/", "order", data, None))
+                .await;
+        }
+
+        let result = waiter.get_result().await.unwrap();
+        assert_eq!(result.0, "\"filled-1\"");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_timeout() {
+        let (connection, _) = create_connection();
+        let (observer, waiter) = create_waiter(Duration::from_secs(30));
+
+        observer
+            .on_event(&create_event(
+                &connection,
+                "/",
+                "order",
+                "\"pending\"",
+                None,
+            ))
+            .await;
+
+        assert_eq!(
+            waiter.get_result().await.err(),
+            Some(SocketIoWaitEventError::Timeout)
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_disconnect_resolves_waiter() {
+        let (connection, _) = create_connection();
+        let (_observer, mut waiter) = create_waiter(Duration::from_secs(30));
+        waiter.set_connection(connection.clone());
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            connection.handle_disconnect().await;
+        });
+
+        let started = tokio::time::Instant::now();
+
+        assert_eq!(
+            waiter.get_result().await.err(),
+            Some(SocketIoWaitEventError::Disconnected)
+        );
+        assert!(started.elapsed() < Duration::from_secs(30));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_waiter_created_on_disconnected_connection_fails_at_once() {
+        let (connection, _) = create_connection();
+        connection.handle_disconnect().await;
+
+        let (_observer, mut waiter) = create_waiter(Duration::from_secs(30));
+        waiter.set_connection(connection);
+
+        assert_eq!(
+            waiter.get_result().await.err(),
+            Some(SocketIoWaitEventError::Disconnected)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dropped_observer_cancels_waiter() {
+        let (observer, waiter) = create_waiter(Duration::from_secs(30));
+        drop(observer);
+
+        assert_eq!(
+            waiter.get_result().await.err(),
+            Some(SocketIoWaitEventError::Cancelled)
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, Semaphore};
+
+use crate::{
+    notify_subscriber, SocketIoAckSender, SocketIoEventSubscriberNotification,
+    SocketIoIncomingEvent, SocketIoPanicReporter,
+};
+
+/// How the subscriber handler is executed relative to the socket read loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SocketIoExecutionMode {
+    /// Handler is awaited by the read loop. Slow handler delays every following frame
+    Inline,
+    /// Events are processed one by one in the order of arrival on the dedicated task.
+    /// Up to 1024 events wait in the queue, then the read loop waits
+    Sequential,
+    /// Up to N events are processed in parallel. When all workers are busy the read loop waits
+    BoundedParallel(usize),
+    /// Every event is processed on its own task
+    SpawnPerEvent,
+}
+
+/// Capacity of the queue of every worker task. The read loop waits when the queue is full
+pub(crate) const SEQUENTIAL_QUEUE_SIZE: usize = 1024;
+
+type SubscriberNotification = Arc<dyn SocketIoEventSubscriberNotification + Send + Sync + 'static>;
+
+enum SocketIoExecutorInner {
+    Sequential(mpsc::Sender<(SocketIoIncomingEvent, Option<SocketIoAckSender>)>),
+    BoundedParallel(Arc<Semaphore>),
+    SpawnPerEvent,
+}
+
+/// Runs the subscriber according to [`SocketIoExecutionMode`]
+pub(crate) struct SocketIoExecutor {
+    subscriber: SubscriberNotification,
+    inner: SocketIoExecutorInner,
+    panic_reporter: Arc<SocketIoPanicReporter>,
+}
+
+impl SocketIoExecutor {
+    /// Has to be called inside the tokio runtime
+    pub fn create(
+        mode: SocketIoExecutionMode,
+        subscriber: SubscriberNotification,
+        panic_reporter: Arc<SocketIoPanicReporter>,
+    ) -> SubscriberNotification {
+        let inner = match mode {
+            SocketIoExecutionMode::Inline => return subscriber,
+            SocketIoExecutionMode::Sequential => {
+                let (sender, mut receiver) = mpsc::channel::<(
+                    SocketIoIncomingEvent,
+                    Option<SocketIoAckSender>,
+                )>(SEQUENTIAL_QUEUE_SIZE);
+
+                let subscriber = subscriber.clone();
+                let panic_reporter = panic_reporter.clone();
+
+                // Ends when the executor is dropped on unsubscribe
+                tokio::spawn(async move {
+                    while let Some((event, ack)) = receiver.recv().await {
+                        handle_event(&subscriber, &event, ack, &panic_reporter).await;
+                    }
+                });
+
+                SocketIoExecutorInner::Sequential(sender)
+            }
+            SocketIoExecutionMode::BoundedParallel(workers) => {
+                SocketIoExecutorInner::BoundedParallel(Arc::new(Semaphore::new(workers.max(1))))
+            }
+            SocketIoExecutionMode::SpawnPerEvent => SocketIoExecutorInner::SpawnPerEvent,
+        };
+
+        Arc::new(Self {
+            subscriber,
+            inner,
+            panic_reporter,
+        })
+    }
+}
+
+/// Panic of the handler is reported and does not stop the worker
+async fn handle_event(
+    subscriber: &SubscriberNotification,
+    event: &SocketIoIncomingEvent,
+    ack: Option<SocketIoAckSender>,
+    panic_reporter: &SocketIoPanicReporter,
+) {
+    if let Err(panic_message) = notify_subscriber(subscriber.as_ref(), event, ack).await {
+        panic_reporter.report(event, panic_message).await;
+    }
+}
+
+#[async_trait::async_trait]
+impl SocketIoEventSubscriberNotification for SocketIoExecutor {
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        match &self.inner {
+            SocketIoExecutorInner::Sequential(sender) => {
+                let _ = sender.send((event.clone(), ack)).await;
+            }
+            SocketIoExecutorInner::BoundedParallel(semaphore) => {
+                let Ok(permit) = semaphore.clone().acquire_owned().await else {
+                    return;
+                };
+
+                let subscriber = self.subscriber.clone();
+                let panic_reporter = self.panic_reporter.clone();
+                let event = event.clone();
+
+                tokio::spawn(async move {
+                    handle_event(&subscriber, &event, ack, &panic_reporter).await;
+                    drop(permit);
+                });
+            }
+            SocketIoExecutorInner::SpawnPerEvent => {
+                let subscriber = self.subscriber.clone();
+                let panic_reporter = self.panic_reporter.clone();
+                let event = event.clone();
+
+                tokio::spawn(async move {
+                    handle_event(&subscriber, &event, ack, &panic_reporter).await;
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        },
+        time::Duration,
+    };
+
+    use tokio::sync::Semaphore;
+
+    use super::*;
+    use crate::socket_io_test_utils::*;
+
+    /// Handler waits for the permit of the gate, so the test decides when it completes
+    struct GatedSubscriber {
+        gate: Semaphore,
+        active: AtomicUsize,
+        max_active: AtomicUsize,
+        log: Mutex<Vec<String>>,
+    }
+
+    impl GatedSubscriber {
+        fn new(permits: usize) -> Arc<Self> {
+            Arc::new(Self {
+                gate: Semaphore::new(permits),
+                active: AtomicUsize::new(0),
+                max_active: AtomicUsize::new(0),
+                log: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn get_log(&self) -> Vec<String> {
+            self.log.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl SocketIoEventSubscriberNotification for GatedSubscriber {
+        async fn on_event(&self, event: &SocketIoIncomingEvent, _: Option<SocketIoAckSender>) {
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_active.fetch_max(active, Ordering::SeqCst);
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("start:{}", event.data));
+
+            self.gate.acquire().await.unwrap().forget();
+            tokio::task::yield_now().await;
+
+            self.log.lock().unwrap().push(format!("end:{}", event.data));
+            self.active.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    fn create_executor(
+        mode: SocketIoExecutionMode,
+        subscriber: &Arc<GatedSubscriber>,
+    ) -> SubscriberNotification {
+        SocketIoExecutor::create(
+            mode,
+            subscriber.clone(),
+            Arc::new(SocketIoPanicReporter::new(Arc::new(TestLogger::default()))),
+        )
+    }
+
+    async fn wait_for_log(subscriber: &GatedSubscriber, len: usize) -> Vec<String> {
+        for _ in 0..1000 {
+            if subscriber.get_log().len() >= len {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        subscriber.get_log()
+    }
+
+    #[tokio::test]
+    async fn test_sequential_keeps_order() {
+        let (connection, _) = create_connection();
+        let subscriber = GatedSubscriber::new(usize::MAX >> 4);
+        let executor = create_executor(SocketIoExecutionMode::Sequential, &subscriber);
+
+        for i in 0..5 {
+            let event = create_event(&connection, "/", "order", i.to_string().as_str(), None);
+            executor.on_event(&event, None).await;
+        }
+
+        let expected: Vec<String> = (0..5)
+            .flat_map(|i| [format!("start:{}", i), format!("end:{}", i)])
+            .collect();
+
+        assert_eq!(wait_for_log(&subscriber, 10).await, expected);
+        assert_eq!(subscriber.max_active.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bounded_parallel_limits_concurrency() {
+        let (connection, _) = create_connection();
+        let subscriber = GatedSubscriber::new(0);
+        let executor = create_executor(SocketIoExecutionMode::BoundedParallel(2), &subscriber);
+
+        for i in 0..2 {
+            let event = create_event(&connection, "/", "order", i.to_string().as_str(), None);
+            executor.on_event(&event, None).await;
+        }
+
+        // Both workers are busy, so the third event waits for the free one
+        let event = create_event(&connection, "/", "order", "2", None);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), executor.on_event(&event, None))
+                .await
+                .is_err()
+        );
+
+        subscriber.gate.add_permits(6);
+
+        for i in 2..6 {
+            let event = create_event(&connection, "/", "order", i.to_string().as_str(), None);
+            executor.on_event(&event, None).await;
+        }
+
+        assert_eq!(wait_for_log(&subscriber, 12).await.len(), 12);
+        assert_eq!(subscriber.max_active.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sequential_queue_applies_backpressure() {
+        let (connection, _) = create_connection();
+        let subscriber = GatedSubscriber::new(0);
+        let executor = create_executor(SocketIoExecutionMode::Sequential, &subscriber);
+
+        let event = create_event(&connection, "/", "order", "0", None);
+        executor.on_event(&event, None).await;
+        wait_for_log(&subscriber, 1).await;
+
+        // The first event is taken by the worker, the next ones fill the queue
+        for i in 1..=SEQUENTIAL_QUEUE_SIZE {
+            let event = create_event(&connection, "/", "order", i.to_string().as_str(), None);
+            executor.on_event(&event, None).await;
+        }
+
+        let event = create_event(&connection, "/", "order", "overflow", None);
+        let mut overflow = Box::pin(executor.on_event(&event, None));
+
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), &mut overflow)
+                .await
+                .is_err()
+        );
+
+        subscriber.gate.add_permits(1);
+        tokio::time::timeout(Duration::from_secs(1), overflow)
+            .await
+            .unwrap();
+
+        subscriber.gate.add_permits(SEQUENTIAL_QUEUE_SIZE + 1);
+
+        let log = wait_for_log(&subscriber, 2 * (SEQUENTIAL_QUEUE_SIZE + 2)).await;
+        assert_eq!(log.len(), 2 * (SEQUENTIAL_QUEUE_SIZE + 2));
+        assert_eq!(log.last().unwrap(), "end:overflow");
+    }
+}
@@ -0,0 +1,477 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use tokio::sync::Notify;
+
+use crate::{SocketIoAckSender, SocketIoConnection};
+
+/// What happens to the incoming event when the inbound queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SocketIoOverflowPolicy {
+    /// Read loop waits until there is a free slot, so the server is slowed down by TCP backpressure.
+    /// Read loop does not read acks while it waits, so a handler awaiting the ack of its own emit
+    /// deadlocks on the full queue. Use timeouts in such handlers or one of the drop policies
+    Block,
+    /// The oldest queued event is dropped
+    DropOldest,
+    /// The incoming event is dropped
+    DropNewest,
+    /// The connection is closed
+    Disconnect,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SocketIoInboundQueueSettings {
+    pub capacity: usize,
+    pub overflow_policy: SocketIoOverflowPolicy,
+}
+
+#[derive(Default)]
+pub struct SocketIoInboundQueueMetrics {
+    depth: AtomicUsize,
+    max_depth: AtomicUsize,
+    dropped: AtomicU64,
+}
+
+impl SocketIoInboundQueueMetrics {
+    /// Amount of events waiting for the handlers
+    pub fn get_depth(&self) -> usize {
+        self.depth.load(Ordering::Relaxed)
+    }
+
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth.load(Ordering::Relaxed)
+    }
+
+    /// Amount of events dropped because of the overflow
+    pub fn get_dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    fn set_depth(&self, depth: usize) {
+        self.depth.store(depth, Ordering::Relaxed);
+        self.max_depth.fetch_max(depth, Ordering::Relaxed);
+    }
+}
+
+/// Event as it came from the socket. Keeps the connection it came from to answer the ack
+pub(crate) struct SocketIoInboundEvent {
+    pub namespace: String,
+    pub event_name: String,
+    pub data: String,
+    pub ack: Option<i64>,
+    pub received_at: DateTimeAsMicroseconds,
+    pub connection: Arc<SocketIoConnection>,
+}
+
+/// Item of the inbound queue. Knows how to answer its ack when it is dropped by the overflow
+#[async_trait::async_trait]
+pub(crate) trait SocketIoInboundQueueItem {
+    async fn reject(self);
+    async fn disconnect(&self);
+}
+
+#[async_trait::async_trait]
+impl SocketIoInboundQueueItem for SocketIoInboundEvent {
+    async fn reject(self) {
+        if let Some(ack) = self.ack {
+            SocketIoAckSender::new(self.connection, self.namespace, self.event_name, ack)
+                .send_error("Event was dropped by the inbound queue")
+                .await;
+        }
+    }
+
+    async fn disconnect(&self) {
+        self.connection.disconnect().await;
+    }
+}
+
+/// Bounded queue between the socket read loop and the handlers
+pub(crate) struct SocketIoInboundQueue<TItem = SocketIoInboundEvent> {
+    settings: SocketIoInboundQueueSettings,
+    items: Mutex<VecDeque<TItem>>,
+    has_items: Notify,
+    has_space: Notify,
+    closed: AtomicBool,
+    metrics: Arc<SocketIoInboundQueueMetrics>,
+}
+
+impl<TItem: SocketIoInboundQueueItem + Send + 'static> SocketIoInboundQueue<TItem> {
+    pub fn new(settings: SocketIoInboundQueueSettings) -> Self {
+        Self {
+            settings: SocketIoInboundQueueSettings {
+                capacity: settings.capacity.max(1),
+                overflow_policy: settings.overflow_policy,
+            },
+            items: Mutex::new(VecDeque::new()),
+            has_items: Notify::new(),
+            has_space: Notify::new(),
+            closed: AtomicBool::new(false),
+            metrics: Arc::new(SocketIoInboundQueueMetrics::default()),
+        }
+    }
+
+    /// Stops the worker waiting in [`SocketIoInboundQueue::pop`] and the read loop blocked
+    /// on the full queue. Events pushed after the close are rejected
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+        self.has_items.notify_one();
+        self.has_space.notify_one();
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+
+    pub fn get_metrics(&self) -> Arc<SocketIoInboundQueueMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Returns the event if there is no free slot
+    fn try_push(&self, event: TItem) -> Result<(), TItem> {
+        let mut items = self.items.lock().unwrap();
+
+        if items.len() >= self.settings.capacity {
+            return Err(event);
+        }
+
+        items.push_back(event);
+        self.metrics.set_depth(items.len());
+        self.has_items.notify_one();
+
+        Ok(())
+    }
+
+    pub async fn push(&self, event: TItem) {
+        if self.is_closed() {
+            event.reject().await;
+            return;
+        }
+
+        let event = match self.try_push(event) {
+            Ok(()) => return,
+            Err(event) => event,
+        };
+
+        match self.settings.overflow_policy {
+            SocketIoOverflowPolicy::Block => {
+                let mut event = event;
+
+                loop {
+                    self.has_space.notified().await;
+
+                    if self.is_closed() {
+                        event.reject().await;
+                        return;
+                    }
+
+                    event = match self.try_push(event) {
+                        Ok(()) => return,
+                        Err(event) => event,
+                    };
+                }
+            }
+            SocketIoOverflowPolicy::DropOldest => {
+                let oldest = {
+                    let mut items = self.items.lock().unwrap();
+                    let oldest = items.pop_front();
+                    items.push_back(event);
+                    self.metrics.set_depth(items.len());
+                    oldest
+                };
+
+                self.has_items.notify_one();
+
+                if let Some(oldest) = oldest {
+                    self.metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                    oldest.reject().await;
+                }
+            }
+            SocketIoOverflowPolicy::DropNewest => {
+                self.metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                event.reject().await;
+            }
+            SocketIoOverflowPolicy::Disconnect => {
+                self.metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                event.disconnect().await;
+            }
+        }
+    }
+
+    /// Returns None when the queue is closed
+    pub async fn pop(&self) -> Option<TItem> {
+        loop {
+            if self.is_closed() {
+                return None;
+            }
+
+            let event = {
+                let mut items = self.items.lock().unwrap();
+                let event = items.pop_front();
+                self.metrics.set_depth(items.len());
+                event
+            };
+
+            if let Some(event) = event {
+                self.has_space.notify_one();
+                return Some(event);
+            }
+
+            self.has_items.notified().await;
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct SocketIoInboundQueues {
+    pub client: Option<Arc<SocketIoInboundQueue>>,
+    pub by_namespace: std::collections::HashMap<String, Arc<SocketIoInboundQueue>>,
+}
+
+impl SocketIoInboundQueues {
+    /// Queue of the namespace has priority over the queue of the client
+    pub fn get(&self, namespace: &str) -> Option<Arc<SocketIoInboundQueue>> {
+        if let Some(queue) = self.by_namespace.get(namespace) {
+            return Some(queue.clone());
+        }
+
+        self.client.clone()
+    }
+
+    pub fn close_all(&self) {
+        for queue in self.client.iter().chain(self.by_namespace.values()) {
+            queue.close();
+        }
+    }
+
+    pub fn get_all(&self) -> Vec<Arc<SocketIoInboundQueue>> {
+        self.client
+            .iter()
+            .chain(self.by_namespace.values())
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{socket_io_ack_sender::error_payload, socket_io_test_utils::*};
+
+    #[derive(Clone, Default)]
+    struct TestLog {
+        rejected: Arc<Mutex<Vec<u32>>>,
+        disconnected: Arc<Mutex<Vec<u32>>>,
+    }
+
+    struct TestEvent {
+        id: u32,
+        log: TestLog,
+    }
+
+    #[async_trait::async_trait]
+    impl SocketIoInboundQueueItem for TestEvent {
+        async fn reject(self) {
+            self.log.rejected.lock().unwrap().push(self.id);
+        }
+
+        async fn disconnect(&self) {
+            self.log.disconnected.lock().unwrap().push(self.id);
+        }
+    }
+
+    fn create_queue(
+        capacity: usize,
+        overflow_policy: SocketIoOverflowPolicy,
+    ) -> SocketIoInboundQueue<TestEvent> {
+        SocketIoInboundQueue::new(SocketIoInboundQueueSettings {
+            capacity,
+            overflow_policy,
+        })
+    }
+
+    async fn push(queue: &SocketIoInboundQueue<TestEvent>, log: &TestLog, ids: &[u32]) {
+        for id in ids {
+            queue
+                .push(TestEvent {
+                    id: *id,
+                    log: log.clone(),
+                })
+                .await;
+        }
+    }
+
+    async fn pop(queue: &SocketIoInboundQueue<TestEvent>) -> u32 {
+        queue.pop().await.unwrap().id
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest() {
+        let log = TestLog::default();
+        let queue = create_queue(2, SocketIoOverflowPolicy::DropOldest);
+
+        push(&queue, &log, &[1, 2, 3]).await;
+
+        assert_eq!(*log.rejected.lock().unwrap(), vec![1]);
+        assert_eq!(queue.get_metrics().get_dropped(), 1);
+        assert_eq!(pop(&queue).await, 2);
+        assert_eq!(pop(&queue).await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_drop_newest() {
+        let log = TestLog::default();
+        let queue = create_queue(2, SocketIoOverflowPolicy::DropNewest);
+
+        push(&queue, &log, &[1, 2, 3]).await;
+
+        assert_eq!(*log.rejected.lock().unwrap(), vec![3]);
+        assert_eq!(queue.get_metrics().get_dropped(), 1);
+        assert_eq!(pop(&queue).await, 1);
+        assert_eq!(pop(&queue).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_disconnect() {
+        let log = TestLog::default();
+        let queue = create_queue(1, SocketIoOverflowPolicy::Disconnect);
+
+        push(&queue, &log, &[1, 2]).await;
+
+        assert_eq!(*log.disconnected.lock().unwrap(), vec![2]);
+        assert!(log.rejected.lock().unwrap().is_empty());
+        assert_eq!(queue.get_metrics().get_dropped(), 1);
+        assert_eq!(pop(&queue).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_block_waits_for_free_slot() {
+        let log = TestLog::default();
+        let queue = Arc::new(create_queue(1, SocketIoOverflowPolicy::Block));
+
+        push(&queue, &log, &[1]).await;
+
+        let pusher = {
+            let queue = queue.clone();
+            let log = log.clone();
+            tokio::spawn(async move { push(&queue, &log, &[2]).await })
+        };
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!pusher.is_finished());
+
+        assert_eq!(pop(&queue).await, 1);
+        pusher.await.unwrap();
+
+        assert_eq!(pop(&queue).await, 2);
+        assert_eq!(queue.get_metrics().get_dropped(), 0);
+        assert!(log.rejected.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_depth_metrics() {
+        let log = TestLog::default();
+        let queue = create_queue(4, SocketIoOverflowPolicy::DropNewest);
+        let metrics = queue.get_metrics();
+
+        push(&queue, &log, &[1, 2, 3]).await;
+        assert_eq!(metrics.get_depth(), 3);
+
+        pop(&queue).await;
+        pop(&queue).await;
+        assert_eq!(metrics.get_depth(), 1);
+        assert_eq!(metrics.get_max_depth(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_close_stops_waiting_worker_and_blocked_push() {
+        let log = TestLog::default();
+        let queue = Arc::new(create_queue(1, SocketIoOverflowPolicy::Block));
+
+        let worker = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.pop().await.map(|event| event.id) })
+        };
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        queue.close();
+
+        let result = tokio::time::timeout(Duration::from_secs(1), worker).await;
+        assert_eq!(result.unwrap().unwrap(), None);
+
+        let queue = Arc::new(create_queue(1, SocketIoOverflowPolicy::Block));
+        push(&queue, &log, &[1]).await;
+
+        let pusher = {
+            let queue = queue.clone();
+            let log = log.clone();
+            tokio::spawn(async move { push(&queue, &log, &[2]).await })
+        };
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        queue.close();
+
+        tokio::time::timeout(Duration::from_secs(1), pusher)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(*log.rejected.lock().unwrap(), vec![2]);
+        assert!(queue.pop().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_inbound_event_is_rejected_with_ack_or_disconnects() {
+        let (connection, transport) = create_connection();
+
+        let queue: SocketIoInboundQueue = SocketIoInboundQueue::new(SocketIoInboundQueueSettings {
+            capacity: 1,
+            overflow_policy: SocketIoOverflowPolicy::DropNewest,
+        });
+
+        for ack in [1, 2] {
+            queue
+                .push(create_inbound_event(
+                    &connection,
+                    "/",
+                    "order",
+                    "1",
+                    Some(ack),
+                ))
+                .await;
+        }
+
+        assert_eq!(
+            transport.get_acks(),
+            vec![(2, error_payload("Event was dropped by the inbound queue"))]
+        );
+
+        let queue: SocketIoInboundQueue = SocketIoInboundQueue::new(SocketIoInboundQueueSettings {
+            capacity: 1,
+            overflow_policy: SocketIoOverflowPolicy::Disconnect,
+        });
+
+        for ack in [3, 4] {
+            queue
+                .push(create_inbound_event(
+                    &connection,
+                    "/",
+                    "order",
+                    "1",
+                    Some(ack),
+                ))
+                .await;
+        }
+
+        assert_eq!(transport.get_disconnects(), 1);
+        assert_eq!(transport.get_acks().len(), 1);
+    }
+}
@@ -0,0 +1,260 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use tokio::sync::mpsc;
+
+use crate::{
+    catch_unwind, SocketIoAckSender, SocketIoEventSubscriberCallback,
+    SocketIoEventSubscriberNotification, SocketIoIncomingEvent, SocketIoPanicReporter,
+    SocketIoSubscribeOutModel, SocketIoSubscribePayloadModel, SEQUENTIAL_QUEUE_SIZE,
+};
+
+type KeyedItem<TModel> = (TModel, SocketIoIncomingEvent, Option<SocketIoAckSender>);
+
+/// Events with the same key are processed strictly in order on the same worker.
+/// Events with different keys are spread across the workers and are processed in parallel.
+/// The read loop waits when 1024 events are queued to the worker
+pub struct SocketIoKeyedSubscriber<TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static> {
+    get_shard: Box<dyn Fn(&TModel) -> usize + Send + Sync + 'static>,
+    workers: Vec<mpsc::Sender<KeyedItem<TModel>>>,
+}
+
+impl<TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static>
+    SocketIoKeyedSubscriber<TModel>
+{
+    /// Has to be called inside the tokio runtime
+    pub(crate) fn new<TKey: Hash, TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static>(
+        workers_amount: usize,
+        key_extractor: impl Fn(&TModel) -> TKey + Send + Sync + 'static,
+        callbacks: Arc<
+            dyn SocketIoEventSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static,
+        >,
+        panic_reporter: Arc<SocketIoPanicReporter>,
+    ) -> Self {
+        let workers_amount = workers_amount.max(1);
+
+        let mut workers = Vec::with_capacity(workers_amount);
+
+        for _ in 0..workers_amount {
+            let (sender, mut receiver) = mpsc::channel::<KeyedItem<TModel>>(SEQUENTIAL_QUEUE_SIZE);
+
+            let callbacks = callbacks.clone();
+            let panic_reporter = panic_reporter.clone();
+
+            // Ends when the subscriber is dropped on unsubscribe
+            tokio::spawn(async move {
+                while let Some((event_model, event, ack)) = receiver.recv().await {
+                    match catch_unwind(callbacks.on_event(event_model)).await {
+                        Ok(response) => {
+                            if let Some(ack) = ack {
+                                ack.send(&response).await;
+                            }
+                        }
+                        Err(panic_message) => {
+                            if let Some(ack) = ack {
+                                ack.send_error("Handler panicked").await;
+                            }
+
+                            panic_reporter.report(&event, panic_message).await;
+                        }
+                    }
+                }
+            });
+
+            workers.push(sender);
+        }
+
+        Self {
+            get_shard: Box::new(move |event_model| {
+                let mut hasher = DefaultHasher::new();
+                key_extractor(event_model).hash(&mut hasher);
+                (hasher.finish() % workers_amount as u64) as usize
+            }),
+            workers,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<TModel: SocketIoSubscribePayloadModel + Send + Sync + 'static>
+    SocketIoEventSubscriberNotification for SocketIoKeyedSubscriber<TModel>
+{
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        let event_model = match TModel::try_deserialize(event.data.as_str()) {
+            Ok(event_model) => event_model,
+            Err(err) => return crate::reject_event(ack, err).await,
+        };
+        let shard = (self.get_shard)(&event_model);
+        let _ = self.workers[shard]
+            .send((event_model, event.clone(), ack))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::Mutex, time::Duration};
+
+    use tokio::sync::Semaphore;
+
+    use super::*;
+    use crate::{socket_io_test_utils::*, SocketIoEventSubscriberCallback};
+
+    /// Payload is "key:seq"
+    struct KeyedModel {
+        key: String,
+        seq: usize,
+    }
+
+    impl SocketIoSubscribePayloadModel for KeyedModel {
+        fn deserialize(payload: &str) -> Self {
+            let (key, seq) = payload.split_once(':').unwrap();
+
+            Self {
+                key: key.to_string(),
+                seq: seq.parse().unwrap(),
+            }
+        }
+    }
+
+    /// Handler of the blocked key waits for the permit of the gate
+    struct KeyedHandler {
+        blocked_key: Option<String>,
+        gate: Semaphore,
+        handled: Mutex<Vec<(String, usize)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SocketIoEventSubscriberCallback<KeyedModel, ()> for KeyedHandler {
+        async fn on_event(&self, event_payload: KeyedModel) {
+            if self.blocked_key.as_deref() == Some(event_payload.key.as_str()) {
+                self.gate.acquire().await.unwrap().forget();
+            }
+
+            // Later events of the key are faster, so reordering would be visible
+            for _ in 0..(10 - event_payload.seq % 10) {
+                tokio::task::yield_now().await;
+            }
+
+            self.handled
+                .lock()
+                .unwrap()
+                .push((event_payload.key, event_payload.seq));
+        }
+    }
+
+    fn create_subscriber(
+        workers_amount: usize,
+        blocked_key: Option<&str>,
+    ) -> (SocketIoKeyedSubscriber<KeyedModel>, Arc<KeyedHandler>) {
+        let handler = Arc::new(KeyedHandler {
+            blocked_key: blocked_key.map(|itm| itm.to_string()),
+            gate: Semaphore::new(0),
+            handled: Mutex::new(Vec::new()),
+        });
+
+        let subscriber = SocketIoKeyedSubscriber::new(
+            workers_amount,
+            |model: &KeyedModel| model.key.clone(),
+            handler.clone(),
+            Arc::new(SocketIoPanicReporter::new(Arc::new(TestLogger::default()))),
+        );
+
+        (subscriber, handler)
+    }
+
+    fn get_shard(subscriber: &SocketIoKeyedSubscriber<KeyedModel>, key: &str) -> usize {
+        (subscriber.get_shard)(&KeyedModel {
+            key: key.to_string(),
+            seq: 0,
+        })
+    }
+
+    async fn wait_for_handled(handler: &KeyedHandler, count: usize) -> Vec<(String, usize)> {
+        for _ in 0..1000 {
+            if handler.handled.lock().unwrap().len() >= count {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        handler.handled.lock().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn test_events_of_the_key_are_handled_in_order() {
+        let (connection, _) = create_connection();
+        let (subscriber, handler) = create_subscriber(4, None);
+
+        let keys = ["EURUSD", "GBPUSD", "USDJPY", "BTCUSD"];
+
+        for seq in 0..20 {
+            for key in keys {
+                let data = format!("{}:{}", key, seq);
+                let event = create_event(&connection, "/", "price", data.as_str(), None);
+                subscriber.on_event(&event, None).await;
+            }
+        }
+
+        let handled = wait_for_handled(&handler, 80).await;
+        assert_eq!(handled.len(), 80);
+
+        for key in keys {
+            let sequence: Vec<usize> = handled
+                .iter()
+                .filter(|(handled_key, _)| handled_key == key)
+                .map(|(_, seq)| *seq)
+                .collect();
+
+            assert_eq!(sequence, (0..20).collect::<Vec<_>>());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_spread_across_workers() {
+        let (subscriber, _) = create_subscriber(8, None);
+
+        let shards: HashSet<usize> = (0..100)
+            .map(|i| get_shard(&subscriber, format!("key-{}", i).as_str()))
+            .collect();
+
+        assert_eq!(shards.len(), 8);
+        assert_eq!(
+            get_shard(&subscriber, "key-1"),
+            get_shard(&subscriber, "key-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_slow_key_does_not_block_keys_of_other_workers() {
+        let (connection, _) = create_connection();
+        let (subscriber, handler) = create_subscriber(4, Some("slow"));
+
+        let slow_shard = get_shard(&subscriber, "slow");
+        let fast_key = (0..)
+            .map(|i| format!("fast-{}", i))
+            .find(|key| get_shard(&subscriber, key.as_str()) != slow_shard)
+            .unwrap();
+
+        for data in ["slow:0".to_string(), format!("{}:0", fast_key)] {
+            let event = create_event(&connection, "/", "price", data.as_str(), None);
+            subscriber.on_event(&event, None).await;
+        }
+
+        assert_eq!(
+            wait_for_handled(&handler, 1).await,
+            vec![(fast_key.clone(), 0)]
+        );
+
+        handler.gate.add_permits(1);
+
+        assert_eq!(
+            wait_for_handled(&handler, 2).await,
+            vec![(fast_key, 0), ("slow".to_string(), 0)]
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use crate::{SocketIoAnyEventSubscriber, SocketIoIncomingEvent, SocketIoSubscribeOutModel};
+
+/// Enum of all the events of the namespace. Each variant maps to an event name.
+/// Unknown event names have to be mapped to the fallback variant.
+/// Malformed payload is returned as the error which answers the ack
+pub trait SocketIoNamespaceEventModel: Sized {
+    const NAME_SPACE: &'static str;
+    fn try_deserialize(event_name: &str, payload: &str) -> Result<Self, String>;
+}
+
+/// Payload part of the namespace event model. Is used when namespace is known only at runtime
+pub trait SocketIoNamespacePayloadModel: Sized {
+    fn try_deserialize(event_name: &str, payload: &str) -> Result<Self, String>;
+}
+
+impl<T: SocketIoNamespaceEventModel> SocketIoNamespacePayloadModel for T {
+    fn try_deserialize(event_name: &str, payload: &str) -> Result<Self, String> {
+        <T as SocketIoNamespaceEventModel>::try_deserialize(event_name, payload)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait SocketIoNamespaceSubscriberCallback<
+    TInModel: SocketIoNamespacePayloadModel + Send + Sync + 'static,
+    TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+>
+{
+    async fn on_event(&self, event: TInModel) -> TOutModel;
+}
+
+pub struct SocketIoNamespaceSubscriber<
+    TModel: SocketIoNamespacePayloadModel + Send + Sync + 'static,
+    TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+> {
+    pub callbacks:
+        Arc<dyn SocketIoNamespaceSubscriberCallback<TModel, TOutModel> + Send + Sync + 'static>,
+}
+
+#[async_trait::async_trait]
+impl<
+        TModel: SocketIoNamespacePayloadModel + Send + Sync + 'static,
+        TOutModel: SocketIoSubscribeOutModel + Send + Sync + 'static,
+    > SocketIoAnyEventSubscriber for SocketIoNamespaceSubscriber<TModel, TOutModel>
+{
+    async fn on_event(&self, event: &SocketIoIncomingEvent) -> Option<String> {
+        // Namespace subscriber is the any-subscriber, so the rejection is returned as the ack payload
+        let event_model =
+            match TModel::try_deserialize(event.ctx.event_name.as_str(), event.data.as_str()) {
+                Ok(event_model) => event_model,
+                Err(err) => return Some(crate::error_payload(err.as_str())),
+            };
+
+        let response = self.callbacks.on_event(event_model).await;
+        Some(
+            response
+                .try_serialize()
+                .unwrap_or_else(|err| crate::error_payload(err.as_str())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::socket_io_test_utils::*;
+
+    #[derive(Debug, PartialEq)]
+    enum OrdersEvent {
+        Created(i32),
+        Unknown(String),
+    }
+
+    impl SocketIoNamespaceEventModel for OrdersEvent {
+        const NAME_SPACE: &'static str = "/orders";
+
+        fn try_deserialize(event_name: &str, payload: &str) -> Result<Self, String> {
+            match event_name {
+                "created" => payload
+                    .parse()
+                    .map(Self::Created)
+                    .map_err(|_| format!("Invalid order id {}", payload)),
+                _ => Ok(Self::Unknown(event_name.to_string())),
+            }
+        }
+    }
+
+    struct OrdersHandler {
+        received: Arc<Mutex<Vec<OrdersEvent>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SocketIoNamespaceSubscriberCallback<OrdersEvent, Accepted> for OrdersHandler {
+        async fn on_event(&self, event: OrdersEvent) -> Accepted {
+            self.received.lock().unwrap().push(event);
+            Accepted
+        }
+    }
+
+    struct Accepted;
+
+    impl SocketIoSubscribeOutModel for Accepted {
+        fn serialize(&self) -> String {
+            "true".to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_are_routed_and_malformed_payload_is_rejected() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let inner = create_client_inner(Arc::new(TestLogger::default()));
+        let (connection, transport) = create_connection();
+
+        let subscriber = SocketIoNamespaceSubscriber {
+            callbacks: Arc::new(OrdersHandler {
+                received: received.clone(),
+            }),
+        };
+
+        inner
+            .register_any_subscriber(Some("/orders".to_string()), Arc::new(subscriber))
+            .await;
+
+        for (event_name, data, ack) in [
+            ("created", "5", Some(1)),
+            ("created", "x", Some(2)),
+            ("deleted", "5", None),
+        ] {
+            inner
+                .dispatch_event(create_inbound_event(
+                    &connection,
+                    "/orders",
+                    event_name,
+                    data,
+                    ack,
+                ))
+                .await;
+        }
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![
+                OrdersEvent::Created(5),
+                OrdersEvent::Unknown("deleted".to_string())
+            ]
+        );
+        assert_eq!(
+            transport.get_acks(),
+            vec![
+                (1, "true".to_string()),
+                (2, crate::error_payload("Invalid order id x"))
+            ]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+/// Namespace pattern like `/accounts/{accountId}`. Each `{name}` segment matches exactly one namespace segment
+#[derive(Debug, Clone)]
+pub struct SocketIoNamespaceTemplate {
+    template: String,
+    segments: Vec<NamespaceTemplateSegment>,
+}
+
+#[derive(Debug, Clone)]
+enum NamespaceTemplateSegment {
+    Static(String),
+    Param(String),
+}
+
+impl SocketIoNamespaceTemplate {
+    pub fn new(template: &str) -> Self {
+        let segments = template
+            .split('/')
+            .map(|segment| {
+                if segment.len() > 2 && segment.starts_with('{') && segment.ends_with('}') {
+                    NamespaceTemplateSegment::Param(segment[1..segment.len() - 1].to_string())
+                } else {
+                    NamespaceTemplateSegment::Static(segment.to_string())
+                }
+            })
+            .collect();
+
+        Self {
+            template: template.to_string(),
+            segments,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.template.as_str()
+    }
+
+    pub fn try_match(&self, namespace: &str) -> Option<SocketIoNamespaceParams> {
+        let mut params = SocketIoNamespaceParams::default();
+
+        let mut namespace_segments = namespace.split('/');
+
+        for segment in self.segments.iter() {
+            let value = namespace_segments.next()?;
+
+            match segment {
+                NamespaceTemplateSegment::Static(expected) => {
+                    if expected != value {
+                        return None;
+                    }
+                }
+                NamespaceTemplateSegment::Param(name) => {
+                    if value.is_empty() {
+                        return None;
+                    }
+
+                    params.items.push((name.to_string(), value.to_string()));
+                }
+            }
+        }
+
+        if namespace_segments.next().is_some() {
+            return None;
+        }
+
+        Some(params)
+    }
+}
+
+/// Parameters extracted from the namespace by [`SocketIoNamespaceTemplate`]
+#[derive(Debug, Clone, Default)]
+pub struct SocketIoNamespaceParams {
+    items: Vec<(String, String)>,
+}
+
+impl SocketIoNamespaceParams {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.items
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.items
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_are_extracted() {
+        let template = SocketIoNamespaceTemplate::new("/accounts/{accountId}/orders/{orderId}");
+
+        let params = template.try_match("/accounts/acc-1/orders/42").unwrap();
+
+        assert_eq!(params.get("accountId"), Some("acc-1"));
+        assert_eq!(params.get("orderId"), Some("42"));
+        assert_eq!(params.get("unknown"), None);
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("accountId", "acc-1"), ("orderId", "42")]
+        );
+    }
+
+    #[test]
+    fn test_literal_segments() {
+        let template = SocketIoNamespaceTemplate::new("/prices/live");
+
+        let params = template.try_match("/prices/live").unwrap();
+        assert!(params.is_empty());
+
+        assert!(template.try_match("/prices/history").is_none());
+    }
+
+    #[test]
+    fn test_non_matching_paths() {
+        let template = SocketIoNamespaceTemplate::new("/accounts/{accountId}");
+
+        assert!(template.try_match("/accounts").is_none());
+        assert!(template.try_match("/accounts/").is_none());
+        assert!(template.try_match("/accounts/acc-1/orders").is_none());
+        assert!(template.try_match("/users/acc-1").is_none());
+        assert!(template.try_match("accounts/acc-1").is_none());
+    }
+
+    #[test]
+    fn test_braces_without_name_are_literal() {
+        let template = SocketIoNamespaceTemplate::new("/a/{}");
+
+        assert!(template.try_match("/a/{}").is_some());
+        assert!(template.try_match("/a/b").is_none());
+    }
+}
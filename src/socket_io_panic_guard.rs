@@ -0,0 +1,256 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    future::Future,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use rust_extensions::{date_time::DateTimeAsMicroseconds, Logger};
+
+use crate::{SocketIoAckSender, SocketIoEventSubscriberNotification, SocketIoIncomingEvent};
+
+/// Event which handler panicked
+#[derive(Debug, Clone)]
+pub struct SocketIoDeadLetter {
+    pub namespace: String,
+    pub event_name: String,
+    pub data: String,
+    pub ack_id: Option<i64>,
+    pub received_at: DateTimeAsMicroseconds,
+    pub panic_message: String,
+}
+
+#[async_trait::async_trait]
+pub trait SocketIoDeadLetterCallback {
+    async fn on_dead_letter(&self, dead_letter: SocketIoDeadLetter);
+}
+
+/// Resolves with the panic message if the future panicked
+pub(crate) struct CatchUnwind<F: Future + Unpin> {
+    future: F,
+}
+
+impl<F: Future + Unpin> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, String>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = &mut self.future;
+
+        match std::panic::catch_unwind(AssertUnwindSafe(|| Pin::new(future).poll(cx))) {
+            Ok(Poll::Ready(result)) => Poll::Ready(Ok(result)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(panic) => Poll::Ready(Err(get_panic_message(panic))),
+        }
+    }
+}
+
+pub(crate) fn catch_unwind<F: Future + Unpin>(future: F) -> CatchUnwind<F> {
+    CatchUnwind { future }
+}
+
+/// Runs the subscriber catching its panic. Ack sender owned by the panicked handler is dropped
+/// after the panic is caught, so the ack is answered with "Handler panicked"
+pub(crate) async fn notify_subscriber(
+    subscriber: &(dyn SocketIoEventSubscriberNotification + Send + Sync),
+    event: &SocketIoIncomingEvent,
+    ack: Option<SocketIoAckSender>,
+) -> Result<(), String> {
+    let panic_marker = ack.as_ref().map(|ack| ack.get_panic_marker());
+
+    let mut handler = subscriber.on_event(event, ack);
+    let result = catch_unwind(&mut handler).await;
+
+    if let (Err(_), Some(panic_marker)) = (&result, panic_marker) {
+        panic_marker.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    drop(handler);
+    result
+}
+
+fn get_panic_message(panic: Box<dyn Any + Send>) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+
+    if let Some(message) = panic.downcast_ref::<String>() {
+        return message.clone();
+    }
+
+    "Unknown panic".to_string()
+}
+
+/// Writes panics of the handlers to the logger and to the dead letter callback
+pub(crate) struct SocketIoPanicReporter {
+    logger: Arc<dyn Logger + Send + Sync + 'static>,
+    dead_letter_callback:
+        Mutex<Option<Arc<dyn SocketIoDeadLetterCallback + Send + Sync + 'static>>>,
+}
+
+impl SocketIoPanicReporter {
+    pub fn new(logger: Arc<dyn Logger + Send + Sync + 'static>) -> Self {
+        Self {
+            logger,
+            dead_letter_callback: Mutex::new(None),
+        }
+    }
+
+    pub fn set_dead_letter_callback(
+        &self,
+        callback: Arc<dyn SocketIoDeadLetterCallback + Send + Sync + 'static>,
+    ) {
+        *self.dead_letter_callback.lock().unwrap() = Some(callback);
+    }
+
+    pub async fn report(&self, event: &SocketIoIncomingEvent, panic_message: String) {
+        let mut ctx = HashMap::new();
+        ctx.insert("namespace".to_string(), event.ctx.namespace.clone());
+        ctx.insert("eventName".to_string(), event.ctx.event_name.clone());
+
+        self.logger.write_error(
+            "SocketIoHandler".to_string(),
+            format!("Handler panicked. Msg: {}", panic_message),
+            Some(ctx),
+        );
+
+        let callback = self.dead_letter_callback.lock().unwrap().clone();
+
+        if let Some(callback) = callback {
+            let dead_letter = SocketIoDeadLetter {
+                namespace: event.ctx.namespace.clone(),
+                event_name: event.ctx.event_name.clone(),
+                data: event.data.clone(),
+                ack_id: event.ctx.ack_id,
+                received_at: event.ctx.received_at,
+                panic_message,
+            };
+
+            // Panic of the dead letter callback itself must not break the read loop
+            let _ = catch_unwind(callback.on_dead_letter(dead_letter)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{socket_io_ack_sender::error_payload, socket_io_test_utils::*};
+
+    async fn handle(delivered: Arc<AtomicUsize>, value: usize) {
+        tokio::task::yield_now().await;
+
+        if value == 0 {
+            panic!("Value can not be {}", value);
+        }
+
+        delivered.fetch_add(value, Ordering::Relaxed);
+    }
+
+    #[tokio::test]
+    async fn test_panic_is_reported_and_next_event_is_delivered() {
+        let delivered = Arc::new(AtomicUsize::new(0));
+        let mut panics = Vec::new();
+
+        for value in [1, 0, 2] {
+            if let Err(panic_message) =
+                catch_unwind(Box::pin(handle(delivered.clone(), value))).await
+            {
+                panics.push(panic_message);
+            }
+        }
+
+        assert_eq!(panics, vec!["Value can not be 0".to_string()]);
+        assert_eq!(delivered.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn test_static_panic_message() {
+        let result = catch_unwind(Box::pin(async { panic!("Handler failed") })).await;
+        assert_eq!(result, Err::<(), _>("Handler failed".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_result_of_not_panicked_future_is_returned() {
+        let result = catch_unwind(Box::pin(async {
+            tokio::task::yield_now().await;
+            5
+        }))
+        .await;
+
+        assert_eq!(result, Ok(5));
+    }
+
+    #[tokio::test]
+    async fn test_ack_of_panicked_subscriber_is_answered_with_handler_panicked() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let (connection, transport) = create_connection();
+        let subscriber = TestSubscriber::panicking("first", "1", &log);
+
+        let result = notify_subscriber(
+            subscriber.as_ref(),
+            &create_event(&connection, "/", "order", "1", Some(3)),
+            Some(create_ack_sender(&connection, 3)),
+        )
+        .await;
+
+        assert_eq!(result, Err("first failed on 1".to_string()));
+        assert_eq!(
+            transport.wait_for_acks(1).await,
+            vec![(3, error_payload("Handler panicked"))]
+        );
+        assert!(log.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_subscriber_answers_ack_when_it_does_not_panic() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let (connection, transport) = create_connection();
+        let subscriber = TestSubscriber::panicking("first", "1", &log);
+
+        let result = notify_subscriber(
+            subscriber.as_ref(),
+            &create_event(&connection, "/", "order", "2", Some(4)),
+            Some(create_ack_sender(&connection, 4)),
+        )
+        .await;
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(transport.get_acks(), vec![(4, "\"first\"".to_string())]);
+        assert_eq!(*log.lock().unwrap(), vec!["first:2"]);
+    }
+
+    #[tokio::test]
+    async fn test_report_writes_error_and_calls_dead_letter_callback() {
+        let logger = Arc::new(TestLogger::default());
+        let callback = Arc::new(TestDeadLetterCallback::default());
+        let (connection, _) = create_connection();
+
+        let reporter = SocketIoPanicReporter::new(logger.clone());
+        reporter.set_dead_letter_callback(callback.clone());
+
+        reporter
+            .report(
+                &create_event(&connection, "/orders", "order", "1", Some(5)),
+                "first failed on 1".to_string(),
+            )
+            .await;
+
+        assert_eq!(
+            logger.get_errors(),
+            vec!["Handler panicked. Msg: first failed on 1"]
+        );
+
+        let dead_letters = callback.get_dead_letters();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].namespace, "/orders");
+        assert_eq!(dead_letters[0].event_name, "order");
+        assert_eq!(dead_letters[0].data, "1");
+        assert_eq!(dead_letters[0].ack_id, Some(5));
+        assert_eq!(dead_letters[0].panic_message, "first failed on 1");
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::{
+    SocketIoAckSender, SocketIoEventContext, SocketIoEventSubscriberNotification,
+    SocketIoIncomingEvent,
+};
+
+/// Subscriber without the typed model. Receives the arguments of the event as json values.
+/// Returned values are the arguments of the ack
+#[async_trait::async_trait]
+pub trait SocketIoRawSubscriberCallback {
+    async fn on_event(&self, ctx: &SocketIoEventContext, args: Vec<Value>) -> Vec<Value>;
+}
+
+pub struct SocketIoRawSubscriber {
+    pub callbacks: Arc<dyn SocketIoRawSubscriberCallback + Send + Sync + 'static>,
+}
+
+#[async_trait::async_trait]
+impl SocketIoEventSubscriberNotification for SocketIoRawSubscriber {
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        let args = match event.get_args() {
+            Ok(args) => args,
+            Err(err) => {
+                if let Some(ack) = ack {
+                    ack.send_error(format!("Payload is not a valid json. Err: {}", err).as_str())
+                        .await;
+                }
+
+                return;
+            }
+        };
+
+        let response = self.callbacks.on_event(&event.ctx, args).await;
+
+        if let Some(ack) = ack {
+            ack.send_raw(crate::join_args(response.as_slice())).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, time::Duration};
+
+    use super::*;
+    use crate::socket_io_test_utils::*;
+
+    struct Proxy {
+        received: Mutex<Vec<Vec<Value>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SocketIoRawSubscriberCallback for Proxy {
+        async fn on_event(&self, _: &SocketIoEventContext, args: Vec<Value>) -> Vec<Value> {
+            self.received.lock().unwrap().push(args.clone());
+            args.into_iter().rev().collect()
+        }
+    }
+
+    fn create_subscriber() -> (SocketIoRawSubscriber, Arc<Proxy>) {
+        let proxy = Arc::new(Proxy {
+            received: Mutex::new(Vec::new()),
+        });
+
+        let subscriber = SocketIoRawSubscriber {
+            callbacks: proxy.clone(),
+        };
+
+        (subscriber, proxy)
+    }
+
+    #[tokio::test]
+    async fn test_arguments_are_passed_through() {
+        let (connection, transport) = create_connection();
+        let (subscriber, proxy) = create_subscriber();
+
+        let event = create_event(
+            &connection,
+            "/",
+            "price:EURUSD",
+            r#"1,"a",{"b":[true]}"#,
+            Some(1),
+        );
+        subscriber
+            .on_event(&event, Some(create_ack_sender(&connection, 1)))
+            .await;
+
+        let event = create_event(&connection, "/", "price:EURUSD", r#"{"bid":1.5}"#, None);
+        subscriber.on_event(&event, None).await;
+
+        assert_eq!(
+            *proxy.received.lock().unwrap(),
+            vec![
+                vec![
+                    serde_json::json!(1),
+                    serde_json::json!("a"),
+                    serde_json::json!({ "b": [true] })
+                ],
+                vec![serde_json::json!({ "bid": 1.5 })]
+            ]
+        );
+        assert_eq!(
+            transport.get_acks(),
+            vec![(1, r#"{"b":[true]},"a",1"#.to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_json_is_rejected() {
+        let (connection, transport) = create_connection();
+        let (subscriber, proxy) = create_subscriber();
+
+        let event = create_event(&connection, "/", "price", r#"{"bid":"#, Some(1));
+        subscriber
+            .on_event(&event, Some(create_ack_sender(&connection, 1)))
+            .await;
+
+        assert!(proxy.received.lock().unwrap().is_empty());
+
+        let acks = transport.get_acks();
+        assert_eq!(acks.len(), 1);
+        assert!(acks[0].1.contains("Payload is not a valid json"));
+    }
+
+    #[tokio::test]
+    async fn test_emit_raw_sends_value_as_is() {
+        let (connection, transport) = create_connection();
+
+        connection
+            .emit_raw("/brand-socket", "command", &serde_json::json!({ "id": 1 }))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transport.get_sent(),
+            vec![TestSentMessage::Event {
+                namespace: "/brand-socket".to_string(),
+                event_name: "command".to_string(),
+                data: r#"{"id":1}"#.to_string(),
+                ack: None,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_emit_raw_with_ack_returns_ack_arguments() {
+        let (connection, transport) = create_connection();
+
+        let emit_connection = connection.clone();
+        let result = tokio::spawn(async move {
+            emit_connection
+                .emit_raw_with_ack("/brand-socket", "command", &serde_json::json!([1, 2]))
+                .await
+        });
+
+        let ack = loop {
+            let sent = transport.get_sent();
+
+            if let Some(TestSentMessage::Event { data, ack, .. }) = sent.first() {
+                assert_eq!(data, "[1,2]");
+                break ack.unwrap();
+            }
+
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        };
+
+        connection
+            .handle_ack_event("/brand-socket", ack, r#"true,"ok""#.to_string())
+            .await;
+
+        assert_eq!(
+            result.await.unwrap().unwrap(),
+            vec![serde_json::json!(true), serde_json::json!("ok")]
+        );
+    }
+}
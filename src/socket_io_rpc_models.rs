@@ -2,8 +2,41 @@ pub trait SocketIoRpcInModel {
     const NAME_SPACE: &'static str;
     const EVENT_NAME: &'static str;
     fn serialize(&self) -> String;
+
+    /// Emits use it to return the error instead of panicking
+    fn try_serialize(&self) -> Result<String, String> {
+        Ok(<Self as SocketIoRpcInModel>::serialize(self))
+    }
+}
+
+/// Payload part of the rpc model. Is used when namespace and event name are known only at runtime
+pub trait SocketIoRpcPayloadModel {
+    fn serialize(&self) -> String;
+
+    /// Emits use it to return the error instead of panicking
+    fn try_serialize(&self) -> Result<String, String> {
+        Ok(<Self as SocketIoRpcPayloadModel>::serialize(self))
+    }
+}
+
+impl<T: SocketIoRpcInModel> SocketIoRpcPayloadModel for T {
+    fn serialize(&self) -> String {
+        <T as SocketIoRpcInModel>::serialize(self)
+    }
+
+    fn try_serialize(&self) -> Result<String, String> {
+        <T as SocketIoRpcInModel>::try_serialize(self)
+    }
 }
 
 pub trait SocketIoRpcOutModel {
     fn deserialize(payload: &str) -> Self;
+
+    /// Awaiting of the ack returns the error instead of panicking on the malformed payload
+    fn try_deserialize(payload: &str) -> Result<Self, String>
+    where
+        Self: Sized,
+    {
+        Ok(Self::deserialize(payload))
+    }
 }
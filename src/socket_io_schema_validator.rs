@@ -0,0 +1,454 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use rust_extensions::{Logger, StrOrString};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SocketIoPayloadDirection {
+    Incoming,
+    Outgoing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SocketIoSchemaViolationPolicy {
+    /// Incoming event is not dispatched (error is sent if ack is requested), outgoing event is not sent
+    Reject,
+    /// Payload goes through, violation is written to the logger
+    Warn,
+    /// Payload goes through, violation is only counted
+    Count,
+}
+
+#[derive(Debug, Clone)]
+pub struct SocketIoSchemaError {
+    /// JSON pointer to the invalid value inside the payload
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SocketIoSchemaViolation {
+    pub direction: SocketIoPayloadDirection,
+    pub namespace: String,
+    pub event_name: String,
+    pub payload: String,
+    pub errors: Vec<SocketIoSchemaError>,
+}
+
+impl std::fmt::Display for SocketIoSchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Payload of {}:{} does not match the schema.",
+            self.namespace, self.event_name
+        )?;
+
+        for error in self.errors.iter() {
+            write!(f, " [{}] {};", error.path, error.message)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub trait SocketIoSchemaViolationCallback {
+    fn on_violation(&self, violation: &SocketIoSchemaViolation);
+}
+
+/// Validates payloads of (namespace, event) pairs against JSON schemas.
+/// Events without registered schema are not validated
+pub struct SocketIoSchemaValidator {
+    schemas: RwLock<HashMap<(SocketIoPayloadDirection, String, String), jsonschema::Validator>>,
+    policy: SocketIoSchemaViolationPolicy,
+    violations: AtomicU64,
+    violation_callback: Option<Arc<dyn SocketIoSchemaViolationCallback + Send + Sync + 'static>>,
+    logger: Arc<dyn Logger + Send + Sync + 'static>,
+}
+
+impl SocketIoSchemaValidator {
+    pub fn new(
+        policy: SocketIoSchemaViolationPolicy,
+        logger: Arc<dyn Logger + Send + Sync + 'static>,
+    ) -> Self {
+        Self {
+            schemas: RwLock::new(HashMap::new()),
+            policy,
+            violations: AtomicU64::new(0),
+            violation_callback: None,
+            logger,
+        }
+    }
+
+    pub fn set_violation_callback(
+        mut self,
+        callback: Arc<dyn SocketIoSchemaViolationCallback + Send + Sync + 'static>,
+    ) -> Self {
+        self.violation_callback = Some(callback);
+        self
+    }
+
+    pub fn add_schema(
+        &self,
+        direction: SocketIoPayloadDirection,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        schema: &serde_json::Value,
+    ) -> Result<(), String> {
+        let namespace: StrOrString<'static> = namespace.into();
+        let event_name: StrOrString<'static> = event_name.into();
+
+        let validator = jsonschema::validator_for(schema).map_err(|err| {
+            format!(
+                "Invalid schema for {}:{}. Err: {}",
+                namespace.as_str(),
+                event_name.as_str(),
+                err
+            )
+        })?;
+
+        self.schemas.write().unwrap().insert(
+            (
+                direction,
+                namespace.as_str().to_string(),
+                event_name.as_str().to_string(),
+            ),
+            validator,
+        );
+
+        Ok(())
+    }
+
+    pub fn add_incoming_schema(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        schema: &serde_json::Value,
+    ) -> Result<(), String> {
+        self.add_schema(
+            SocketIoPayloadDirection::Incoming,
+            namespace,
+            event_name,
+            schema,
+        )
+    }
+
+    pub fn add_outgoing_schema(
+        &self,
+        namespace: impl Into<StrOrString<'static>>,
+        event_name: impl Into<StrOrString<'static>>,
+        schema: &serde_json::Value,
+    ) -> Result<(), String> {
+        self.add_schema(
+            SocketIoPayloadDirection::Outgoing,
+            namespace,
+            event_name,
+            schema,
+        )
+    }
+
+    pub fn get_policy(&self) -> SocketIoSchemaViolationPolicy {
+        self.policy
+    }
+
+    pub fn get_violations_count(&self) -> u64 {
+        self.violations.load(Ordering::Relaxed)
+    }
+
+    /// Returns error if the payload has to be rejected according to the policy
+    pub(crate) fn check(
+        &self,
+        direction: SocketIoPayloadDirection,
+        namespace: &str,
+        event_name: &str,
+        payload: &str,
+    ) -> Result<(), SocketIoSchemaViolation> {
+        let errors = {
+            let schemas = self.schemas.read().unwrap();
+
+            let Some(validator) =
+                schemas.get(&(direction, namespace.to_string(), event_name.to_string()))
+            else {
+                return Ok(());
+            };
+
+            validate_payload(validator, payload)
+        };
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        let violation = SocketIoSchemaViolation {
+            direction,
+            namespace: namespace.to_string(),
+            event_name: event_name.to_string(),
+            payload: payload.to_string(),
+            errors,
+        };
+
+        self.violations.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(callback) = self.violation_callback.as_ref() {
+            callback.on_violation(&violation);
+        }
+
+        match self.policy {
+            SocketIoSchemaViolationPolicy::Reject => Err(violation),
+            SocketIoSchemaViolationPolicy::Warn => {
+                let mut ctx = HashMap::new();
+                ctx.insert("namespace".to_string(), violation.namespace.clone());
+                ctx.insert("eventName".to_string(), violation.event_name.clone());
+                ctx.insert("payload".to_string(), violation.payload.clone());
+
+                self.logger.write_warning(
+                    "SocketIoSchemaValidator".to_string(),
+                    violation.to_string(),
+                    Some(ctx),
+                );
+
+                Ok(())
+            }
+            SocketIoSchemaViolationPolicy::Count => Ok(()),
+        }
+    }
+}
+
+/// Single argument payload is validated as is, multiple arguments are validated as an array
+fn validate_payload(validator: &jsonschema::Validator, payload: &str) -> Vec<SocketIoSchemaError> {
+    let mut args: Vec<serde_json::Value> = match crate::deserialize_args(payload) {
+        Ok(args) => args,
+        Err(err) => {
+            return vec![SocketIoSchemaError {
+                path: String::new(),
+                message: format!("Payload is not a valid json. Err: {}", err),
+            }];
+        }
+    };
+
+    let value = if args.len() == 1 {
+        args.remove(0)
+    } else {
+        serde_json::Value::Array(args)
+    };
+
+    validator
+        .iter_errors(&value)
+        .map(|err| SocketIoSchemaError {
+            path: err.instance_path.to_string(),
+            message: err.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "json-schema"))]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::socket_io_test_utils::TestLogger;
+
+    #[derive(Default)]
+    struct ViolationsSink {
+        violations: Mutex<Vec<SocketIoSchemaViolation>>,
+    }
+
+    impl SocketIoSchemaViolationCallback for ViolationsSink {
+        fn on_violation(&self, violation: &SocketIoSchemaViolation) {
+            self.violations.lock().unwrap().push(violation.clone());
+        }
+    }
+
+    fn create_validator(
+        policy: SocketIoSchemaViolationPolicy,
+    ) -> (
+        SocketIoSchemaValidator,
+        Arc<TestLogger>,
+        Arc<ViolationsSink>,
+    ) {
+        let logger = Arc::new(TestLogger::default());
+        let sink = Arc::new(ViolationsSink::default());
+
+        let validator = SocketIoSchemaValidator::new(policy, logger.clone())
+            .set_violation_callback(sink.clone());
+
+        validator
+            .add_incoming_schema(
+                "/orders",
+                "order",
+                &serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "tags": { "type": "array", "items": { "type": "string" } }
+                    },
+                    "required": ["id"]
+                }),
+            )
+            .unwrap();
+
+        (validator, logger, sink)
+    }
+
+    fn check_order(
+        validator: &SocketIoSchemaValidator,
+        payload: &str,
+    ) -> Result<(), SocketIoSchemaViolation> {
+        validator.check(
+            SocketIoPayloadDirection::Incoming,
+            "/orders",
+            "order",
+            payload,
+        )
+    }
+
+    fn get_paths(violation: &SocketIoSchemaViolation) -> Vec<&str> {
+        violation
+            .errors
+            .iter()
+            .map(|itm| itm.path.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_reject_policy() {
+        let (validator, logger, sink) = create_validator(SocketIoSchemaViolationPolicy::Reject);
+
+        assert!(check_order(&validator, r#"{"id":1,"tags":["a"]}"#).is_ok());
+
+        let violation = check_order(&validator, r#"{"id":"x"}"#).unwrap_err();
+
+        assert_eq!(violation.direction, SocketIoPayloadDirection::Incoming);
+        assert_eq!(violation.namespace, "/orders");
+        assert_eq!(violation.event_name, "order");
+        assert_eq!(violation.payload, r#"{"id":"x"}"#);
+        assert_eq!(get_paths(&violation), vec!["/id"]);
+        assert!(violation
+            .to_string()
+            .starts_with("Payload of /orders:order does not match the schema. [/id]"));
+
+        assert_eq!(validator.get_violations_count(), 1);
+        assert_eq!(sink.violations.lock().unwrap().len(), 1);
+        assert!(logger.get_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_warn_policy() {
+        let (validator, logger, sink) = create_validator(SocketIoSchemaViolationPolicy::Warn);
+
+        assert!(check_order(&validator, r#"{"tags":[]}"#).is_ok());
+
+        assert_eq!(validator.get_violations_count(), 1);
+        assert_eq!(sink.violations.lock().unwrap().len(), 1);
+
+        let warnings = logger.get_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Payload of /orders:order does not match the schema."));
+    }
+
+    #[test]
+    fn test_count_policy() {
+        let (validator, logger, sink) = create_validator(SocketIoSchemaViolationPolicy::Count);
+
+        assert!(check_order(&validator, r#"{"tags":[]}"#).is_ok());
+        assert!(check_order(&validator, r#"{"id":1.5}"#).is_ok());
+
+        assert_eq!(validator.get_violations_count(), 2);
+        assert_eq!(sink.violations.lock().unwrap().len(), 2);
+        assert!(logger.get_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_instance_paths() {
+        let (validator, _, _) = create_validator(SocketIoSchemaViolationPolicy::Reject);
+
+        let violation = check_order(&validator, r#"{"id":"x","tags":["a",2,"b",3]}"#).unwrap_err();
+
+        let mut paths = get_paths(&violation);
+        paths.sort();
+        assert_eq!(paths, vec!["/id", "/tags/1", "/tags/3"]);
+
+        let violation = check_order(&validator, r#"{"id":1"#).unwrap_err();
+        assert_eq!(get_paths(&violation), vec![""]);
+        assert!(violation.errors[0]
+            .message
+            .starts_with("Payload is not a valid json."));
+    }
+
+    #[test]
+    fn test_events_without_schema_are_not_validated() {
+        let (validator, _, sink) = create_validator(SocketIoSchemaViolationPolicy::Reject);
+
+        assert!(validator
+            .check(
+                SocketIoPayloadDirection::Incoming,
+                "/orders",
+                "cancel",
+                "\"x\""
+            )
+            .is_ok());
+        assert!(validator
+            .check(
+                SocketIoPayloadDirection::Incoming,
+                "/other",
+                "order",
+                "\"x\""
+            )
+            .is_ok());
+        assert!(validator
+            .check(
+                SocketIoPayloadDirection::Outgoing,
+                "/orders",
+                "order",
+                "\"x\""
+            )
+            .is_ok());
+
+        assert_eq!(validator.get_violations_count(), 0);
+        assert!(sink.violations.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_multi_argument_payload_is_validated_as_array() {
+        let (validator, _, _) = create_validator(SocketIoSchemaViolationPolicy::Reject);
+
+        validator
+            .add_outgoing_schema(
+                "/game",
+                "move",
+                &serde_json::json!({
+                    "type": "array",
+                    "prefixItems": [{ "type": "integer" }, { "type": "string" }],
+                    "minItems": 2
+                }),
+            )
+            .unwrap();
+
+        let check_move = |payload: &str| {
+            validator.check(SocketIoPayloadDirection::Outgoing, "/game", "move", payload)
+        };
+
+        assert!(check_move(r#"1,"up""#).is_ok());
+
+        let violation = check_move("1,2").unwrap_err();
+        assert_eq!(get_paths(&violation), vec!["/1"]);
+
+        let violation = check_move("1").unwrap_err();
+        assert_eq!(get_paths(&violation), vec![""]);
+    }
+
+    #[test]
+    fn test_invalid_schema_is_rejected() {
+        let (validator, _, _) = create_validator(SocketIoSchemaViolationPolicy::Reject);
+
+        let err = validator
+            .add_incoming_schema("/orders", "cancel", &serde_json::json!({ "type": 5 }))
+            .unwrap_err();
+
+        assert!(err.starts_with("Invalid schema for /orders:cancel."));
+    }
+}
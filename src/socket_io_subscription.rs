@@ -0,0 +1,119 @@
+use std::sync::{Arc, Weak};
+
+use crate::client_inner::ClientInner;
+
+/// Handle of the registered subscriber. Subscriber is unregistered when the handle is dropped
+/// or [`SocketIoSubscription::unsubscribe`] is called.
+/// Use [`SocketIoSubscription::detach`] to keep the subscriber for the whole life of the client
+#[must_use = "Subscriber is unregistered as soon as the subscription is dropped. Call detach() to keep it"]
+pub struct SocketIoSubscription {
+    id: u64,
+    client: Option<Weak<ClientInner>>,
+}
+
+impl SocketIoSubscription {
+    pub(crate) fn new(id: u64, client: &Arc<ClientInner>) -> Self {
+        Self {
+            id,
+            client: Some(Arc::downgrade(client)),
+        }
+    }
+
+    pub async fn unsubscribe(mut self) {
+        let Some(client) = self.client.take().and_then(|client| client.upgrade()) else {
+            return;
+        };
+
+        client.unregister_subscriber(self.id).await;
+    }
+
+    /// Keeps subscriber registered for the whole life of the client
+    pub fn detach(mut self) {
+        self.client = None;
+    }
+}
+
+impl Drop for SocketIoSubscription {
+    fn drop(&mut self) {
+        let Some(client) = self.client.take().and_then(|client| client.upgrade()) else {
+            return;
+        };
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let id = self.id;
+        runtime.spawn(async move {
+            client.unregister_subscriber(id).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, time::Duration};
+
+    use super::*;
+    use crate::{socket_io_test_utils::*, SocketIoEventNamePattern};
+
+    async fn subscribe(
+        client: &Arc<ClientInner>,
+        log: &Arc<Mutex<Vec<String>>>,
+    ) -> SocketIoSubscription {
+        let id = client
+            .register_subscriber(
+                "/".to_string(),
+                SocketIoEventNamePattern::exact("order"),
+                TestSubscriber::new("subscriber", log),
+            )
+            .await;
+
+        SocketIoSubscription::new(id, client)
+    }
+
+    async fn deliver(client: &ClientInner, log: &Arc<Mutex<Vec<String>>>) -> Vec<String> {
+        let (connection, _) = create_connection();
+
+        client
+            .dispatch_event(create_inbound_event(&connection, "/", "order", "1", None))
+            .await;
+
+        std::mem::take(&mut *log.lock().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_subscriber_is_unregistered_when_subscription_is_dropped() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let client = create_client_inner(Arc::new(TestLogger::default()));
+
+        let subscription = subscribe(&client, &log).await;
+        assert_eq!(deliver(&client, &log).await, vec!["subscriber:1"]);
+
+        drop(subscription);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(deliver(&client, &log).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let client = create_client_inner(Arc::new(TestLogger::default()));
+
+        subscribe(&client, &log).await.unsubscribe().await;
+
+        assert!(deliver(&client, &log).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_detached_subscriber_stays_registered() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let client = create_client_inner(Arc::new(TestLogger::default()));
+
+        subscribe(&client, &log).await.detach();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(deliver(&client, &log).await, vec!["subscriber:1"]);
+    }
+}
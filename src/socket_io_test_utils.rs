@@ -0,0 +1,319 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use rust_extensions::{date_time::DateTimeAsMicroseconds, Logger, StrOrString};
+use socket_io_utils::{SocketIoContract, SocketIoMessage};
+
+use crate::{client_inner::ClientInner, *};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestSentMessage {
+    Connect(String),
+    Disconnect(String),
+    Event {
+        namespace: String,
+        event_name: String,
+        data: String,
+        ack: Option<i64>,
+    },
+    Ack {
+        namespace: String,
+        data: String,
+        ack: i64,
+    },
+}
+
+/// Records everything sent to the server instead of writing it to the socket
+#[derive(Default)]
+pub struct TestTransport {
+    sent: Mutex<Vec<TestSentMessage>>,
+    disconnects: AtomicUsize,
+}
+
+impl TestTransport {
+    pub fn get_sent(&self) -> Vec<TestSentMessage> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    pub fn get_acks(&self) -> Vec<(i64, String)> {
+        self.get_sent()
+            .into_iter()
+            .filter_map(|itm| match itm {
+                TestSentMessage::Ack { data, ack, .. } => Some((ack, data)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn get_disconnects(&self) -> usize {
+        self.disconnects.load(Ordering::Relaxed)
+    }
+
+    /// Dropped ack senders answer on the spawned task
+    pub async fn wait_for_acks(&self, count: usize) -> Vec<(i64, String)> {
+        for _ in 0..1000 {
+            let acks = self.get_acks();
+
+            if acks.len() >= count {
+                return acks;
+            }
+
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        self.get_acks()
+    }
+}
+
+#[async_trait::async_trait]
+impl SocketIoTransport for TestTransport {
+    async fn send_message(&self, contract: &SocketIoContract) {
+        let SocketIoContract::Message(message) = contract else {
+            return;
+        };
+
+        let message = match message {
+            SocketIoMessage::Connect { namespace, .. } => {
+                TestSentMessage::Connect(namespace.to_string())
+            }
+            SocketIoMessage::Disconnect { namespace } => {
+                TestSentMessage::Disconnect(namespace.to_string())
+            }
+            SocketIoMessage::Event {
+                namespace,
+                event_name,
+                data,
+                ack,
+            } => TestSentMessage::Event {
+                namespace: namespace.to_string(),
+                event_name: event_name.to_string(),
+                data: data.to_string(),
+                ack: *ack,
+            },
+            SocketIoMessage::Ack {
+                namespace,
+                data,
+                ack,
+                ..
+            } => TestSentMessage::Ack {
+                namespace: namespace.to_string(),
+                data: data.to_string(),
+                ack: *ack,
+            },
+            _ => return,
+        };
+
+        self.sent.lock().unwrap().push(message);
+    }
+
+    async fn disconnect(&self) {
+        self.disconnects.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub fn create_connection() -> (Arc<SocketIoConnection>, Arc<TestTransport>) {
+    let transport = Arc::new(TestTransport::default());
+    let connection = SocketIoConnection::from_transport(transport.clone());
+    (Arc::new(connection), transport)
+}
+
+pub fn create_event(
+    connection: &Arc<SocketIoConnection>,
+    namespace: &str,
+    event_name: &str,
+    data: &str,
+    ack: Option<i64>,
+) -> SocketIoIncomingEvent {
+    SocketIoIncomingEvent {
+        ctx: SocketIoEventContext {
+            namespace: namespace.to_string(),
+            event_name: event_name.to_string(),
+            namespace_params: SocketIoNamespaceParams::default(),
+            ack_id: ack,
+            received_at: DateTimeAsMicroseconds::now(),
+            sid: None,
+            connection: connection.clone(),
+        },
+        data: data.to_string(),
+    }
+}
+
+pub fn create_inbound_event(
+    connection: &Arc<SocketIoConnection>,
+    namespace: &str,
+    event_name: &str,
+    data: &str,
+    ack: Option<i64>,
+) -> SocketIoInboundEvent {
+    SocketIoInboundEvent {
+        namespace: namespace.to_string(),
+        event_name: event_name.to_string(),
+        data: data.to_string(),
+        ack,
+        received_at: DateTimeAsMicroseconds::now(),
+        connection: connection.clone(),
+    }
+}
+
+pub fn create_ack_sender(connection: &Arc<SocketIoConnection>, ack: i64) -> SocketIoAckSender {
+    SocketIoAckSender::new(connection.clone(), "/".to_string(), "test".to_string(), ack)
+}
+
+#[derive(Default)]
+pub struct TestLogger {
+    errors: Mutex<Vec<String>>,
+    warnings: Mutex<Vec<String>>,
+}
+
+impl TestLogger {
+    pub fn get_errors(&self) -> Vec<String> {
+        self.errors.lock().unwrap().clone()
+    }
+
+    #[cfg_attr(not(feature = "json-schema"), allow(dead_code))]
+    pub fn get_warnings(&self) -> Vec<String> {
+        self.warnings.lock().unwrap().clone()
+    }
+}
+
+impl Logger for TestLogger {
+    fn write_info(&self, _: String, _: String, _: Option<HashMap<String, String>>) {}
+
+    fn write_warning(&self, _: String, message: String, _: Option<HashMap<String, String>>) {
+        self.warnings.lock().unwrap().push(message);
+    }
+
+    fn write_error(&self, _: String, message: String, _: Option<HashMap<String, String>>) {
+        self.errors.lock().unwrap().push(message);
+    }
+
+    fn write_fatal_error(&self, _: String, message: String, _: Option<HashMap<String, String>>) {
+        self.errors.lock().unwrap().push(message);
+    }
+
+    fn write_debug_info(&self, _: String, _: String, _: Option<HashMap<String, String>>) {}
+}
+
+#[derive(Default)]
+pub struct TestDeadLetterCallback {
+    dead_letters: Mutex<Vec<SocketIoDeadLetter>>,
+}
+
+impl TestDeadLetterCallback {
+    pub fn get_dead_letters(&self) -> Vec<SocketIoDeadLetter> {
+        self.dead_letters.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl SocketIoDeadLetterCallback for TestDeadLetterCallback {
+    async fn on_dead_letter(&self, dead_letter: SocketIoDeadLetter) {
+        self.dead_letters.lock().unwrap().push(dead_letter);
+    }
+}
+
+pub struct TestSettings;
+
+#[async_trait::async_trait]
+impl SocketIoClientSettings for TestSettings {
+    async fn get_server_url(&self, _: &str) -> String {
+        "http://localhost".to_string()
+    }
+
+    async fn get_handshake_path(&self, _: &str) -> String {
+        "/socket.io/".to_string()
+    }
+
+    async fn get_headers(&self, _: &str) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    async fn get_query_params(&self, _: &str) -> Vec<(String, String)> {
+        vec![]
+    }
+}
+
+pub struct TestCallbacks;
+
+#[async_trait::async_trait]
+impl SocketIoCallbacks for TestCallbacks {
+    async fn on_connect(&self, _: Arc<SocketIoConnection>) {}
+    async fn on_disconnect(&self, _: Arc<SocketIoConnection>) {}
+}
+
+pub fn create_client_inner(logger: Arc<TestLogger>) -> Arc<ClientInner> {
+    let settings = WebSocketIoSettings {
+        socket_io_settings: Arc::new(TestSettings),
+    };
+
+    Arc::new(ClientInner::new(
+        Arc::new(StrOrString::create_as_string("test".to_string())),
+        Arc::new(TestCallbacks),
+        Arc::new(settings),
+        logger,
+    ))
+}
+
+pub fn create_client(logger: Arc<TestLogger>) -> MySocketIoClient {
+    MySocketIoClient::new(
+        "test",
+        Arc::new(TestSettings),
+        Arc::new(TestCallbacks),
+        logger,
+    )
+}
+
+/// Records the events it receives. Answers the ack with its name or panics on the event
+pub struct TestSubscriber {
+    name: &'static str,
+    panic_on: Option<&'static str>,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestSubscriber {
+    pub fn new(name: &'static str, log: &Arc<Mutex<Vec<String>>>) -> Arc<Self> {
+        Arc::new(Self {
+            name,
+            panic_on: None,
+            log: log.clone(),
+        })
+    }
+
+    pub fn panicking(
+        name: &'static str,
+        panic_on: &'static str,
+        log: &Arc<Mutex<Vec<String>>>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            name,
+            panic_on: Some(panic_on),
+            log: log.clone(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl SocketIoEventSubscriberNotification for TestSubscriber {
+    async fn on_event(&self, event: &SocketIoIncomingEvent, ack: Option<SocketIoAckSender>) {
+        tokio::task::yield_now().await;
+
+        if self.panic_on == Some(event.data.as_str()) {
+            panic!("{} failed on {}", self.name, event.data);
+        }
+
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{}:{}", self.name, event.data));
+
+        if let Some(ack) = ack {
+            ack.send_raw(format!("\"{}\"", self.name)).await;
+        }
+    }
+}
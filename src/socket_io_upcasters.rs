@@ -0,0 +1,374 @@
+use std::{collections::HashMap, sync::Arc};
+
+use serde_json::Value;
+
+/// Where the version of the incoming payload is taken from
+pub enum SocketIoPayloadVersionSource {
+    /// Integer field of the payload like `{"v": 2, ...}`. Payload without the field is version 1.
+    /// Multi-argument payload keeps the field in the first argument
+    Field(String),
+    /// Suffix of the event name after the separator like `stream.v2` with `.v` separator.
+    /// Event name without the suffix is version 1
+    EventNameSuffix(String),
+}
+
+/// Transforms the payload of the version it is registered for into the payload of the next version
+pub trait SocketIoPayloadUpcaster {
+    fn upcast(&self, payload: Value) -> Result<Value, String>;
+}
+
+impl<F: Fn(Value) -> Result<Value, String>> SocketIoPayloadUpcaster for F {
+    fn upcast(&self, payload: Value) -> Result<Value, String> {
+        self(payload)
+    }
+}
+
+/// Chain of upcasters bringing older payloads of the event to the current version
+pub struct SocketIoEventVersioning {
+    version_source: SocketIoPayloadVersionSource,
+    current_version: u32,
+    upcasters: HashMap<u32, Arc<dyn SocketIoPayloadUpcaster + Send + Sync + 'static>>,
+}
+
+impl SocketIoEventVersioning {
+    pub fn new(version_source: SocketIoPayloadVersionSource, current_version: u32) -> Self {
+        Self {
+            version_source,
+            current_version,
+            upcasters: HashMap::new(),
+        }
+    }
+
+    /// Registers upcaster from `from_version` to `from_version + 1`
+    pub fn add_upcaster(
+        mut self,
+        from_version: u32,
+        upcaster: impl SocketIoPayloadUpcaster + Send + Sync + 'static,
+    ) -> Self {
+        self.upcasters.insert(from_version, Arc::new(upcaster));
+        self
+    }
+
+    pub fn get_current_version(&self) -> u32 {
+        self.current_version
+    }
+
+    fn get_version(
+        &self,
+        event_name_version: Option<u32>,
+        payload: &Value,
+        single_arg: bool,
+    ) -> u32 {
+        match &self.version_source {
+            SocketIoPayloadVersionSource::Field(field) => get_version_holder(payload, single_arg)
+                .and_then(|itm| itm.get(field.as_str()))
+                .and_then(|itm| itm.as_u64())
+                .map(|itm| itm as u32)
+                .unwrap_or(1),
+            SocketIoPayloadVersionSource::EventNameSuffix(_) => event_name_version.unwrap_or(1),
+        }
+    }
+
+    fn upcast(&self, event_name_version: Option<u32>, payload: &str) -> Result<String, String> {
+        let mut args: Vec<Value> = crate::deserialize_args(payload)
+            .map_err(|err| format!("Payload is not a valid json. Err: {}", err))?;
+
+        let single_arg = args.len() == 1;
+
+        let mut value = if single_arg {
+            args.remove(0)
+        } else {
+            Value::Array(args)
+        };
+
+        let mut version = self.get_version(event_name_version, &value, single_arg);
+
+        if version >= self.current_version {
+            return Ok(payload.to_string());
+        }
+
+        while version < self.current_version {
+            let upcaster = self
+                .upcasters
+                .get(&version)
+                .ok_or_else(|| format!("No upcaster from version {}", version))?;
+
+            value = upcaster.upcast(value)?;
+            version += 1;
+
+            if let SocketIoPayloadVersionSource::Field(field) = &self.version_source {
+                if let Some(Value::Object(object)) = get_version_holder_mut(&mut value, single_arg)
+                {
+                    object.insert(field.to_string(), version.into());
+                }
+            }
+        }
+
+        if single_arg {
+            return Ok(value.to_string());
+        }
+
+        match value {
+            Value::Array(args) => Ok(crate::join_args(args.as_slice())),
+            value => Ok(value.to_string()),
+        }
+    }
+}
+
+/// Single argument holds the version field itself. Multi-argument payload holds it in the first argument
+fn get_version_holder(value: &Value, single_arg: bool) -> Option<&Value> {
+    if single_arg {
+        return Some(value);
+    }
+
+    match value {
+        Value::Array(args) => args.first(),
+        _ => None,
+    }
+}
+
+fn get_version_holder_mut(value: &mut Value, single_arg: bool) -> Option<&mut Value> {
+    if single_arg {
+        return Some(value);
+    }
+
+    match value {
+        Value::Array(args) => args.first_mut(),
+        _ => None,
+    }
+}
+
+pub(crate) struct SocketIoUpcastedEvent {
+    pub event_name: String,
+    pub payload: String,
+}
+
+#[derive(Default)]
+pub(crate) struct SocketIoUpcasters {
+    items: HashMap<(String, String), SocketIoEventVersioning>,
+}
+
+impl SocketIoUpcasters {
+    pub fn insert(
+        &mut self,
+        namespace: String,
+        event_name: String,
+        versioning: SocketIoEventVersioning,
+    ) {
+        self.items.insert((namespace, event_name), versioning);
+    }
+
+    /// Returns None if there is no versioning registered for the event.
+    /// Versioned event name like `stream.v2` is turned into `stream`.
+    /// If several event names are the prefix of the versioned one - the longest one wins
+    pub fn upcast(
+        &self,
+        namespace: &str,
+        event_name: &str,
+        payload: &str,
+    ) -> Option<Result<SocketIoUpcastedEvent, String>> {
+        if let Some(versioning) = self
+            .items
+            .get(&(namespace.to_string(), event_name.to_string()))
+        {
+            let result = versioning
+                .upcast(None, payload)
+                .map(|payload| SocketIoUpcastedEvent {
+                    event_name: event_name.to_string(),
+                    payload,
+                });
+
+            return Some(result);
+        }
+
+        let mut found: Option<(&String, &SocketIoEventVersioning, u32)> = None;
+
+        for ((versioning_namespace, versioning_event_name), versioning) in self.items.iter() {
+            if versioning_namespace != namespace {
+                continue;
+            }
+
+            let SocketIoPayloadVersionSource::EventNameSuffix(separator) =
+                &versioning.version_source
+            else {
+                continue;
+            };
+
+            let Some(version) = event_name
+                .strip_prefix(versioning_event_name.as_str())
+                .and_then(|suffix| suffix.strip_prefix(separator.as_str()))
+                .and_then(|version| version.parse::<u32>().ok())
+            else {
+                continue;
+            };
+
+            if let Some((found_event_name, _, _)) = &found {
+                if found_event_name.len() >= versioning_event_name.len() {
+                    continue;
+                }
+            }
+
+            found = Some((versioning_event_name, versioning, version));
+        }
+
+        let (versioning_event_name, versioning, version) = found?;
+
+        let result =
+            versioning
+                .upcast(Some(version), payload)
+                .map(|payload| SocketIoUpcastedEvent {
+                    event_name: versioning_event_name.to_string(),
+                    payload,
+                });
+
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn rename_name_to_title(mut payload: Value) -> Result<Value, String> {
+        let object = payload.as_object_mut().ok_or("Object is expected")?;
+        let name = object.remove("name").unwrap_or_default();
+        object.insert("title".to_string(), name);
+        Ok(payload)
+    }
+
+    fn upcast(upcasters: &SocketIoUpcasters, event_name: &str, payload: &str) -> (String, Value) {
+        let result = upcasters.upcast("/", event_name, payload).unwrap().unwrap();
+
+        let payload: Value = serde_json::from_str(result.payload.as_str()).unwrap();
+        (result.event_name, payload)
+    }
+
+    #[test]
+    fn test_field_version_is_upcasted() {
+        let mut upcasters = SocketIoUpcasters::default();
+        upcasters.insert(
+            "/".to_string(),
+            "stream".to_string(),
+            SocketIoEventVersioning::new(SocketIoPayloadVersionSource::Field("v".to_string()), 2)
+                .add_upcaster(1, rename_name_to_title),
+        );
+
+        let (event_name, payload) = upcast(&upcasters, "stream", r#"{"name":"a"}"#);
+        assert_eq!(event_name, "stream");
+        assert_eq!(payload, json!({"title": "a", "v": 2}));
+
+        let (_, payload) = upcast(&upcasters, "stream", r#"{"v":2,"title":"b"}"#);
+        assert_eq!(payload, json!({"v": 2, "title": "b"}));
+    }
+
+    #[test]
+    fn test_field_version_of_multi_arg_payload_is_in_first_arg() {
+        let mut upcasters = SocketIoUpcasters::default();
+        upcasters.insert(
+            "/".to_string(),
+            "move".to_string(),
+            SocketIoEventVersioning::new(SocketIoPayloadVersionSource::Field("v".to_string()), 2)
+                .add_upcaster(1, |_| Err("v1 is not expected".to_string())),
+        );
+
+        let result = upcasters
+            .upcast("/", "move", r#"{"v":2,"x":1},{"y":2}"#)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.payload, r#"{"v":2,"x":1},{"y":2}"#);
+    }
+
+    #[test]
+    fn test_multi_arg_payload_is_upcasted_as_array() {
+        let mut upcasters = SocketIoUpcasters::default();
+        upcasters.insert(
+            "/".to_string(),
+            "move".to_string(),
+            SocketIoEventVersioning::new(SocketIoPayloadVersionSource::Field("v".to_string()), 2)
+                .add_upcaster(1, |payload: Value| {
+                    let mut args = payload.as_array().cloned().ok_or("Array is expected")?;
+                    args.push(json!(0));
+                    Ok(Value::Array(args))
+                }),
+        );
+
+        let result = upcasters
+            .upcast("/", "move", r#"{"x":1},{"y":2}"#)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.payload, r#"{"v":2,"x":1},{"y":2},0"#);
+    }
+
+    #[test]
+    fn test_event_name_suffix_is_upcasted() {
+        let mut upcasters = SocketIoUpcasters::default();
+        upcasters.insert(
+            "/".to_string(),
+            "stream".to_string(),
+            SocketIoEventVersioning::new(
+                SocketIoPayloadVersionSource::EventNameSuffix(".v".to_string()),
+                3,
+            )
+            .add_upcaster(1, rename_name_to_title)
+            .add_upcaster(2, |mut payload: Value| {
+                payload["upcasted"] = json!(true);
+                Ok(payload)
+            }),
+        );
+
+        let (event_name, payload) = upcast(&upcasters, "stream", r#"{"name":"a"}"#);
+        assert_eq!(event_name, "stream");
+        assert_eq!(payload, json!({"title": "a", "upcasted": true}));
+
+        let (event_name, payload) = upcast(&upcasters, "stream.v2", r#"{"title":"b"}"#);
+        assert_eq!(event_name, "stream");
+        assert_eq!(payload, json!({"title": "b", "upcasted": true}));
+
+        let (event_name, payload) = upcast(&upcasters, "stream.v3", r#"{"title":"c"}"#);
+        assert_eq!(event_name, "stream");
+        assert_eq!(payload, json!({"title": "c"}));
+    }
+
+    #[test]
+    fn test_longest_event_name_suffix_wins() {
+        let mut upcasters = SocketIoUpcasters::default();
+
+        for (event_name, separator) in [("price", ".v"), ("price.v", "")] {
+            upcasters.insert(
+                "/".to_string(),
+                event_name.to_string(),
+                SocketIoEventVersioning::new(
+                    SocketIoPayloadVersionSource::EventNameSuffix(separator.to_string()),
+                    1,
+                ),
+            );
+        }
+
+        let result = upcasters.upcast("/", "price.v1", "{}").unwrap().unwrap();
+        assert_eq!(result.event_name, "price.v");
+    }
+
+    #[test]
+    fn test_missing_upcaster_is_error() {
+        let mut upcasters = SocketIoUpcasters::default();
+        upcasters.insert(
+            "/".to_string(),
+            "stream".to_string(),
+            SocketIoEventVersioning::new(SocketIoPayloadVersionSource::Field("v".to_string()), 3)
+                .add_upcaster(1, rename_name_to_title),
+        );
+
+        let result = upcasters.upcast("/", "stream", r#"{"name":"a"}"#).unwrap();
+        assert_eq!(result.err(), Some("No upcaster from version 2".to_string()));
+    }
+
+    #[test]
+    fn test_event_without_versioning_is_not_touched() {
+        let upcasters = SocketIoUpcasters::default();
+        assert!(upcasters.upcast("/", "stream", "{}").is_none());
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "macros")]
+
+use my_socket_io_client::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, SocketIoEvent, SocketIoRpcOut)]
+#[socket_io(namespace = "/brand-socket", event = "stream")]
+struct StreamModel {
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, SocketIoEvent, SocketIoOut, SocketIoRpcIn)]
+struct GenericModel<T> {
+    value: T,
+}
+
+#[derive(Serialize, SocketIoRpcIn)]
+#[socket_io(namespace = "/brand-socket", event = "command")]
+struct CommandModel {
+    id: String,
+}
+
+#[test]
+fn test_event_model_names_and_deserialize() {
+    assert_eq!(StreamModel::NAME_SPACE, "/brand-socket");
+    assert_eq!(
+        <StreamModel as SocketIoSubscribeEventModel>::EVENT_NAME,
+        "stream"
+    );
+
+    let model = <StreamModel as SocketIoSubscribeEventModel>::deserialize(r#"{"name":"a"}"#);
+    assert_eq!(
+        model,
+        StreamModel {
+            name: "a".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_try_deserialize_returns_error() {
+    let result = <StreamModel as SocketIoSubscribeEventModel>::try_deserialize("bad");
+    assert!(result
+        .unwrap_err()
+        .contains("Can not deserialize StreamModel"));
+
+    let result = <StreamModel as SocketIoRpcOutModel>::try_deserialize(r#"{"name":1}"#);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_generic_model_round_trip() {
+    let model = GenericModel { value: 5 };
+
+    let payload = SocketIoSubscribeOutModel::try_serialize(&model).unwrap();
+    assert_eq!(payload, r#"{"value":5}"#);
+    assert_eq!(SocketIoRpcPayloadModel::serialize(&model), payload);
+
+    let result: GenericModel<i32> =
+        SocketIoSubscribePayloadModel::try_deserialize(&payload).unwrap();
+    assert_eq!(result, model);
+}
+
+#[test]
+fn test_rpc_in_model() {
+    assert_eq!(CommandModel::NAME_SPACE, "/brand-socket");
+    assert_eq!(CommandModel::EVENT_NAME, "command");
+
+    let model = CommandModel {
+        id: "1".to_string(),
+    };
+
+    assert_eq!(
+        SocketIoRpcInModel::try_serialize(&model).unwrap(),
+        r#"{"id":"1"}"#
+    );
+}